@@ -1,5 +1,7 @@
+use std::cell::Cell;
 use std::fmt::Display;
 
+use crate::ast::{Parameter, Stmt};
 use crate::token::{types::Literal, Token};
 
 #[derive(Clone, Debug, PartialEq)]
@@ -13,6 +15,7 @@ pub enum Expr {
         callee: Box<Expr>,
         paren: Token,
         arguments: Vec<Expr>,
+        named_arguments: Vec<(Token, Expr)>,
     },
     Grouping {
         expression: Box<Expr>,
@@ -26,28 +29,109 @@ pub enum Expr {
     },
     Variable {
         name: Token,
+        depth: Cell<Option<usize>>,
     },
     Assignment {
         name: Token,
         value: Box<Expr>,
+        depth: Cell<Option<usize>>,
+    },
+    Conditional {
+        condition: Box<Expr>,
+        then_branch: Box<Expr>,
+        else_branch: Box<Expr>,
     },
     Logical {
         left: Box<Expr>,
         operator: Token,
         right: Box<Expr>,
     },
+    Range {
+        start: Box<Expr>,
+        operator: Token,
+        end: Box<Expr>,
+        step: Option<Box<Expr>>,
+    },
+    Get {
+        object: Box<Expr>,
+        name: Token,
+    },
+    Set {
+        object: Box<Expr>,
+        name: Token,
+        value: Box<Expr>,
+    },
+    This {
+        keyword: Token,
+        depth: Cell<Option<usize>>,
+    },
+    Super {
+        keyword: Token,
+        method: Token,
+        depth: Cell<Option<usize>>,
+    },
+    List {
+        elements: Vec<Expr>,
+    },
+    Interpolation {
+        parts: Vec<Expr>,
+    },
+    Lambda {
+        keyword: Token,
+        parameters: Vec<Parameter>,
+        body: Vec<Stmt>,
+    },
+    Map {
+        brace: Token,
+        entries: Vec<(Expr, Expr)>,
+    },
+    Index {
+        object: Box<Expr>,
+        bracket: Token,
+        index: Box<Expr>,
+    },
+    IndexSet {
+        object: Box<Expr>,
+        bracket: Token,
+        index: Box<Expr>,
+        value: Box<Expr>,
+    },
+    CompoundAssignment {
+        target: Box<Expr>,
+        operator: Token,
+        value: Box<Expr>,
+    },
+    Increment {
+        target: Box<Expr>,
+        operator: Token,
+        is_prefix: bool,
+    },
     Nil,
 }
 
 pub trait ExprVisitor<R> {
     fn visit_binary_expr(&mut self, left: &Expr, operator: &Token, right: &Expr) -> R;
-    fn visit_call_expr(&mut self, callee: &Expr, paren: &Token, arguments: &Vec<Expr>) -> R;
+    fn visit_call_expr(&mut self, callee: &Expr, paren: &Token, arguments: &Vec<Expr>, named_arguments: &Vec<(Token, Expr)>) -> R;
     fn visit_grouping_expr(&mut self, expression: &Expr) -> R;
+    fn visit_range_expr(&mut self, start: &Expr, operator: &Token, end: &Expr, step: &Option<Box<Expr>>) -> R;
     fn visit_literal_expr(&mut self, value: &Literal) -> R;
     fn visit_unary_expr(&mut self, operator: &Token, right: &Expr) -> R;
-    fn visit_variable_expr(&mut self, name: &Token) -> R;
-    fn visit_assignment_expr(&mut self, name: &Token, value: &Expr) -> R;
+    fn visit_variable_expr(&mut self, name: &Token, depth: &Cell<Option<usize>>) -> R;
+    fn visit_assignment_expr(&mut self, name: &Token, value: &Expr, depth: &Cell<Option<usize>>) -> R;
+    fn visit_conditional_expr(&mut self, condition: &Expr, then_branch: &Expr, else_branch: &Expr) -> R;
     fn visit_logical_expr(&mut self, left: &Expr, operator: &Token, right: &Expr) -> R;
+    fn visit_get_expr(&mut self, object: &Expr, name: &Token) -> R;
+    fn visit_set_expr(&mut self, object: &Expr, name: &Token, value: &Expr) -> R;
+    fn visit_this_expr(&mut self, keyword: &Token, depth: &Cell<Option<usize>>) -> R;
+    fn visit_super_expr(&mut self, keyword: &Token, method: &Token, depth: &Cell<Option<usize>>) -> R;
+    fn visit_list_expr(&mut self, elements: &Vec<Expr>) -> R;
+    fn visit_interpolation_expr(&mut self, parts: &Vec<Expr>) -> R;
+    fn visit_lambda_expr(&mut self, keyword: &Token, parameters: &Vec<Parameter>, body: &Vec<Stmt>) -> R;
+    fn visit_map_expr(&mut self, brace: &Token, entries: &Vec<(Expr, Expr)>) -> R;
+    fn visit_index_expr(&mut self, object: &Expr, bracket: &Token, index: &Expr) -> R;
+    fn visit_index_set_expr(&mut self, object: &Expr, bracket: &Token, index: &Expr, value: &Expr) -> R;
+    fn visit_compound_assignment_expr(&mut self, target: &Expr, operator: &Token, value: &Expr) -> R;
+    fn visit_increment_expr(&mut self, target: &Expr, operator: &Token, is_prefix: bool) -> R;
 }
 
 impl Expr {
@@ -59,11 +143,12 @@ impl Expr {
         };
     }
 
-    pub fn new_call(callee: Expr, paren: Token, arguments: Vec<Expr>) -> Self {
+    pub fn new_call(callee: Expr, paren: Token, arguments: Vec<Expr>, named_arguments: Vec<(Token, Expr)>) -> Self {
         return Expr::Call {
             callee: Box::new(callee),
             paren: paren,
             arguments,
+            named_arguments,
         };
     }
 
@@ -85,13 +170,34 @@ impl Expr {
     }
 
     pub fn new_variable(name: Token) -> Self {
-        return Expr::Variable { name: name };
+        return Expr::Variable {
+            name: name,
+            depth: Cell::new(None),
+        };
     }
 
     pub fn new_assignment(name: Token, value: Expr) -> Self {
         return Expr::Assignment {
             name: name,
             value: Box::new(value),
+            depth: Cell::new(None),
+        };
+    }
+
+    pub fn new_conditional(condition: Expr, then_branch: Expr, else_branch: Expr) -> Self {
+        return Expr::Conditional {
+            condition: Box::new(condition),
+            then_branch: Box::new(then_branch),
+            else_branch: Box::new(else_branch),
+        };
+    }
+
+    pub fn new_range(start: Expr, operator: Token, end: Expr, step: Option<Expr>) -> Self {
+        return Expr::Range {
+            start: Box::new(start),
+            operator,
+            end: Box::new(end),
+            step: step.map(Box::new),
         };
     }
 
@@ -103,16 +209,142 @@ impl Expr {
         };
     }
 
+    pub fn new_get(object: Expr, name: Token) -> Self {
+        return Expr::Get {
+            object: Box::new(object),
+            name,
+        };
+    }
+
+    pub fn new_set(object: Expr, name: Token, value: Expr) -> Self {
+        return Expr::Set {
+            object: Box::new(object),
+            name,
+            value: Box::new(value),
+        };
+    }
+
+    pub fn new_this(keyword: Token) -> Self {
+        return Expr::This {
+            keyword,
+            depth: Cell::new(None),
+        };
+    }
+
+    pub fn new_super(keyword: Token, method: Token) -> Self {
+        return Expr::Super {
+            keyword,
+            method,
+            depth: Cell::new(None),
+        };
+    }
+
+    pub fn new_list(elements: Vec<Expr>) -> Self {
+        return Expr::List { elements };
+    }
+
+    pub fn new_interpolation(parts: Vec<Expr>) -> Self {
+        return Expr::Interpolation { parts };
+    }
+
+    pub fn new_lambda(keyword: Token, parameters: Vec<Parameter>, body: Vec<Stmt>) -> Self {
+        return Expr::Lambda {
+            keyword,
+            parameters,
+            body,
+        };
+    }
+
+    pub fn new_map(brace: Token, entries: Vec<(Expr, Expr)>) -> Self {
+        return Expr::Map { brace, entries };
+    }
+
+    pub fn new_index(object: Expr, bracket: Token, index: Expr) -> Self {
+        return Expr::Index {
+            object: Box::new(object),
+            bracket,
+            index: Box::new(index),
+        };
+    }
+
+    pub fn new_index_set(object: Expr, bracket: Token, index: Expr, value: Expr) -> Self {
+        return Expr::IndexSet {
+            object: Box::new(object),
+            bracket,
+            index: Box::new(index),
+            value: Box::new(value),
+        };
+    }
+
+    pub fn new_compound_assignment(target: Expr, operator: Token, value: Expr) -> Self {
+        return Expr::CompoundAssignment {
+            target: Box::new(target),
+            operator,
+            value: Box::new(value),
+        };
+    }
+
+    pub fn new_increment(target: Expr, operator: Token, is_prefix: bool) -> Self {
+        return Expr::Increment {
+            target: Box::new(target),
+            operator,
+            is_prefix,
+        };
+    }
+
     pub fn accept<R>(&self, visitor: &mut dyn ExprVisitor<R>) -> R {
         match self {
             Expr::Binary { left, operator, right } => visitor.visit_binary_expr(left, operator, right),
-            Expr::Call { callee, paren, arguments } => visitor.visit_call_expr(callee, paren, arguments),
+            Expr::Call {
+                callee,
+                paren,
+                arguments,
+                named_arguments,
+            } => visitor.visit_call_expr(callee, paren, arguments, named_arguments),
             Expr::Grouping { expression } => visitor.visit_grouping_expr(expression),
             Expr::LiteralValue { value } => visitor.visit_literal_expr(value),
             Expr::Unary { operator, right } => visitor.visit_unary_expr(operator, right),
-            Expr::Variable { name } => visitor.visit_variable_expr(name),
-            Expr::Assignment { name, value } => visitor.visit_assignment_expr(name, value),
+            Expr::Variable { name, depth } => visitor.visit_variable_expr(name, depth),
+            Expr::Assignment { name, value, depth } => visitor.visit_assignment_expr(name, value, depth),
+            Expr::Conditional {
+                condition,
+                then_branch,
+                else_branch,
+            } => visitor.visit_conditional_expr(condition, then_branch, else_branch),
             Expr::Logical { left, operator, right } => visitor.visit_logical_expr(left, operator, right),
+            Expr::Range {
+                start,
+                operator,
+                end,
+                step,
+            } => visitor.visit_range_expr(start, operator, end, step),
+            Expr::Get { object, name } => visitor.visit_get_expr(object, name),
+            Expr::Set { object, name, value } => visitor.visit_set_expr(object, name, value),
+            Expr::This { keyword, depth } => visitor.visit_this_expr(keyword, depth),
+            Expr::Super { keyword, method, depth } => visitor.visit_super_expr(keyword, method, depth),
+            Expr::List { elements } => visitor.visit_list_expr(elements),
+            Expr::Interpolation { parts } => visitor.visit_interpolation_expr(parts),
+            Expr::Lambda {
+                keyword,
+                parameters,
+                body,
+            } => visitor.visit_lambda_expr(keyword, parameters, body),
+            Expr::Map { brace, entries } => visitor.visit_map_expr(brace, entries),
+            Expr::Index { object, bracket, index } => visitor.visit_index_expr(object, bracket, index),
+            Expr::IndexSet {
+                object,
+                bracket,
+                index,
+                value,
+            } => visitor.visit_index_set_expr(object, bracket, index, value),
+            Expr::CompoundAssignment { target, operator, value } => {
+                visitor.visit_compound_assignment_expr(target, operator, value)
+            }
+            Expr::Increment {
+                target,
+                operator,
+                is_prefix,
+            } => visitor.visit_increment_expr(target, operator, *is_prefix),
             Expr::Nil => panic!("Can't visit Nil expression!"),
         }
     }
@@ -128,6 +360,7 @@ impl Display for Expr {
                 callee,
                 paren: _,
                 arguments,
+                named_arguments,
             } => {
                 write!(f, "(call {} [", callee)?;
                 for (i, arg) in arguments.iter().enumerate() {
@@ -136,6 +369,12 @@ impl Display for Expr {
                     }
                     write!(f, "{}", arg)?;
                 }
+                for (i, (name, arg)) in named_arguments.iter().enumerate() {
+                    if i > 0 || !arguments.is_empty() {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}: {}", name, arg)?;
+                }
                 write!(f, "])")
             }
             Expr::Grouping { expression } => {
@@ -147,15 +386,104 @@ impl Display for Expr {
             Expr::Unary { operator, right } => {
                 return write!(f, "({} {})", operator.lexeme, right);
             }
-            Expr::Variable { name } => {
+            Expr::Variable { name, depth: _ } => {
                 return write!(f, "{}", name);
             }
-            Expr::Assignment { name, value } => {
+            Expr::Assignment { name, value, depth: _ } => {
                 return write!(f, "{} = {}", name, value);
             }
+            Expr::Conditional {
+                condition,
+                then_branch,
+                else_branch,
+            } => {
+                return write!(f, "(?: {} {} {})", condition, then_branch, else_branch);
+            }
             Expr::Logical { left, operator, right } => {
                 return write!(f, "{} {} {}", left, operator.lexeme, right);
             }
+            Expr::Range {
+                start,
+                operator,
+                end,
+                step,
+            } => match step {
+                Some(step) => return write!(f, "({} {} {} step {})", operator.lexeme, start, end, step),
+                None => return write!(f, "({} {} {})", operator.lexeme, start, end),
+            },
+            Expr::Get { object, name } => {
+                return write!(f, "{}.{}", object, name);
+            }
+            Expr::Set { object, name, value } => {
+                return write!(f, "{}.{} = {}", object, name, value);
+            }
+            Expr::This { keyword, depth: _ } => {
+                return write!(f, "{}", keyword);
+            }
+            Expr::Super { keyword, method, depth: _ } => {
+                return write!(f, "{}.{}", keyword, method);
+            }
+            Expr::List { elements } => {
+                write!(f, "[")?;
+                for (i, element) in elements.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", element)?;
+                }
+                write!(f, "]")
+            }
+            Expr::Interpolation { parts } => {
+                let parts: Vec<String> = parts.iter().map(|part| format!("{}", part)).collect();
+                write!(f, "(str {})", parts.join(" "))
+            }
+            Expr::Lambda {
+                keyword: _,
+                parameters,
+                body,
+            } => {
+                let params: Vec<String> = parameters.iter().map(|param| format!("{}", param)).collect();
+                let body_stmts: Vec<String> = body.iter().map(|stmt| format!("{}", stmt)).collect();
+                write!(f, "(fun [{}] [{}])", params.join(", "), body_stmts.join(", "))
+            }
+            Expr::Map { brace: _, entries } => {
+                write!(f, "{{")?;
+                for (i, (key, value)) in entries.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}: {}", key, value)?;
+                }
+                write!(f, "}}")
+            }
+            Expr::Index {
+                object,
+                bracket: _,
+                index,
+            } => {
+                return write!(f, "{}[{}]", object, index);
+            }
+            Expr::IndexSet {
+                object,
+                bracket: _,
+                index,
+                value,
+            } => {
+                return write!(f, "{}[{}] = {}", object, index, value);
+            }
+            Expr::CompoundAssignment { target, operator, value } => {
+                return write!(f, "{} {} {}", target, operator.lexeme, value);
+            }
+            Expr::Increment {
+                target,
+                operator,
+                is_prefix,
+            } => {
+                if *is_prefix {
+                    return write!(f, "({} {})", operator.lexeme, target);
+                }
+                return write!(f, "({} {})", target, operator.lexeme);
+            }
             Expr::Nil => {
                 return write!(f, "Nil");
             }
@@ -1,7 +1,11 @@
 pub mod expr;
+pub mod parameter;
+pub mod pattern;
 pub mod stmt;
 
 pub use expr::Expr;
 pub use expr::ExprVisitor;
+pub use parameter::Parameter;
+pub use pattern::{MatchArm, Pattern};
 pub use stmt::Stmt;
 pub use stmt::StmtVisitor;
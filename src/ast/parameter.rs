@@ -0,0 +1,44 @@
+use std::fmt::Display;
+
+use crate::ast::Expr;
+use crate::token::Token;
+
+/// Parameter of a function or lambda. A default is evaluated on every call that leaves the parameter out, and a
+/// rest parameter, always the last one, collects the remaining arguments into a list.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Parameter {
+    pub name: Token,
+    pub default: Option<Expr>,
+    pub is_rest: bool,
+}
+
+impl Parameter {
+    pub fn new(name: Token, default: Option<Expr>) -> Self {
+        return Parameter {
+            name,
+            default,
+            is_rest: false,
+        };
+    }
+
+    pub fn new_rest(name: Token) -> Self {
+        return Parameter {
+            name,
+            default: None,
+            is_rest: true,
+        };
+    }
+}
+
+impl Display for Parameter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.is_rest {
+            return write!(f, "...{}", self.name);
+        }
+
+        match &self.default {
+            Some(default) => return write!(f, "{} = {}", self.name, default),
+            None => return write!(f, "{}", self.name),
+        }
+    }
+}
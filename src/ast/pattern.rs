@@ -0,0 +1,83 @@
+use std::fmt::Display;
+
+use crate::ast::{Expr, Stmt};
+use crate::token::types::Literal;
+use crate::token::Token;
+
+/// Pattern of a `match` arm, tested against the value being matched.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Pattern {
+    Literal(Literal),
+    Range { start: f64, end: f64, inclusive: bool },
+    Wildcard,
+    // `Shape.Circle(r)` binds the fields of the variant by position, `Shape.Circle` only checks the variant
+    Variant {
+        enum_name: Expr,
+        variant: Token,
+        fields: Option<Vec<Token>>,
+    },
+}
+
+/// One arm of a `match` statement. The body runs when any of the patterns matches and the guard, if there is
+/// one, is truthy.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MatchArm {
+    pub patterns: Vec<Pattern>,
+    pub guard: Option<Expr>,
+    pub body: Stmt,
+}
+
+impl MatchArm {
+    pub fn new(patterns: Vec<Pattern>, guard: Option<Expr>, body: Stmt) -> Self {
+        return MatchArm { patterns, guard, body };
+    }
+
+    /// Names bound by the arm, only a lone variant pattern with a field list binds any.
+    pub fn bindings(&self) -> Option<&Vec<Token>> {
+        match self.patterns.as_slice() {
+            [Pattern::Variant {
+                fields: Some(fields), ..
+            }] => return Some(fields),
+            _ => return None,
+        }
+    }
+}
+
+impl Display for Pattern {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Pattern::Literal(value) => write!(f, "{}", value),
+            Pattern::Range { start, end, inclusive } => {
+                write!(f, "{}{}{}", start, if *inclusive { "..=" } else { ".." }, end)
+            }
+            Pattern::Wildcard => write!(f, "_"),
+            Pattern::Variant {
+                enum_name,
+                variant,
+                fields,
+            } => {
+                write!(f, "{}.{}", enum_name, variant.lexeme)?;
+                match fields {
+                    Some(fields) => {
+                        let fields: Vec<&str> = fields.iter().map(|field| field.lexeme.as_str()).collect();
+                        write!(f, "({})", fields.join(", "))
+                    }
+                    None => Ok(()),
+                }
+            }
+        }
+    }
+}
+
+impl Display for MatchArm {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let patterns: Vec<String> = self.patterns.iter().map(|pattern| format!("{}", pattern)).collect();
+        write!(f, "{}", patterns.join(" | "))?;
+
+        if let Some(guard) = &self.guard {
+            write!(f, " if {}", guard)?;
+        }
+
+        write!(f, " => {}", self.body)
+    }
+}
@@ -1,4 +1,4 @@
-use crate::ast::Expr;
+use crate::ast::{Expr, MatchArm, Parameter};
 use crate::token::Token;
 
 #[derive(Debug, Clone, PartialEq)]
@@ -18,22 +18,71 @@ pub enum Stmt {
         name: Token,
         initializer: Option<Expr>,
     },
+    ConstStmt {
+        name: Token,
+        initializer: Box<Expr>,
+    },
     BlockStmt {
         declarations: Vec<Stmt>,
     },
+    ForInStmt {
+        keyword: Token,
+        name: Token,
+        iterable: Box<Expr>,
+        body: Box<Stmt>,
+    },
     WhileStmt {
         condition: Box<Expr>,
         body: Box<Stmt>,
+        increment: Option<Box<Expr>>,
     },
     FunctionStmt {
         name: Token,
-        parameters: Vec<Token>,
+        parameters: Vec<Parameter>,
         body: Vec<Stmt>,
     },
     ReturnStmt {
         keyword: Token,
         value: Box<Expr>,
     },
+    ClassStmt {
+        name: Token,
+        superclass: Option<Expr>,
+        methods: Vec<Stmt>,
+    },
+    EnumStmt {
+        name: Token,
+        variants: Vec<(Token, Vec<Token>)>,
+    },
+    BreakStmt {
+        keyword: Token,
+    },
+    ContinueStmt {
+        keyword: Token,
+    },
+    MatchStmt {
+        keyword: Token,
+        subject: Box<Expr>,
+        arms: Vec<MatchArm>,
+    },
+    ImportStmt {
+        keyword: Token,
+        path: String,
+        name: Token,
+    },
+    ThrowStmt {
+        keyword: Token,
+        value: Box<Expr>,
+    },
+    YieldStmt {
+        keyword: Token,
+        value: Box<Expr>,
+    },
+    TryStmt {
+        body: Vec<Stmt>,
+        catch_clause: Option<(Token, Vec<Stmt>)>,
+        finally_body: Option<Vec<Stmt>>,
+    },
 }
 
 pub trait StmtVisitor<R> {
@@ -41,10 +90,21 @@ pub trait StmtVisitor<R> {
     fn visit_if_stmt(&mut self, condition: &Expr, then_branch: &Stmt, else_branch: &Option<Stmt>) -> R;
     fn visit_print_stmt(&mut self, expr: &Expr) -> R;
     fn visit_var_stmt(&mut self, name: &Token, initializer: &Option<Expr>) -> R;
+    fn visit_const_stmt(&mut self, name: &Token, initializer: &Expr) -> R;
     fn visit_block_stmt(&mut self, declarations: &Vec<Stmt>) -> R;
-    fn visit_while_stmt(&mut self, condition: &Expr, body: &Stmt) -> R;
-    fn visit_function_stmt(&mut self, name: &Token, parameters: &Vec<Token>, body: &Vec<Stmt>) -> R;
+    fn visit_for_in_stmt(&mut self, keyword: &Token, name: &Token, iterable: &Expr, body: &Stmt) -> R;
+    fn visit_while_stmt(&mut self, condition: &Expr, body: &Stmt, increment: &Option<Box<Expr>>) -> R;
+    fn visit_function_stmt(&mut self, name: &Token, parameters: &Vec<Parameter>, body: &Vec<Stmt>) -> R;
     fn visit_return_stmt(&mut self, keyword: &Token, value: &Expr) -> R;
+    fn visit_class_stmt(&mut self, name: &Token, superclass: &Option<Expr>, methods: &Vec<Stmt>) -> R;
+    fn visit_enum_stmt(&mut self, name: &Token, variants: &Vec<(Token, Vec<Token>)>) -> R;
+    fn visit_break_stmt(&mut self, keyword: &Token) -> R;
+    fn visit_continue_stmt(&mut self, keyword: &Token) -> R;
+    fn visit_match_stmt(&mut self, keyword: &Token, subject: &Expr, arms: &Vec<MatchArm>) -> R;
+    fn visit_import_stmt(&mut self, keyword: &Token, path: &str, name: &Token) -> R;
+    fn visit_throw_stmt(&mut self, keyword: &Token, value: &Expr) -> R;
+    fn visit_yield_stmt(&mut self, keyword: &Token, value: &Expr) -> R;
+    fn visit_try_stmt(&mut self, body: &Vec<Stmt>, catch_clause: &Option<(Token, Vec<Stmt>)>, finally_body: &Option<Vec<Stmt>>) -> R;
 }
 
 impl Stmt {
@@ -71,20 +131,37 @@ impl Stmt {
         };
     }
 
+    pub fn new_const_stmt(name: Token, initializer: Expr) -> Self {
+        return Stmt::ConstStmt {
+            name,
+            initializer: Box::new(initializer),
+        };
+    }
+
+    pub fn new_for_in_stmt(keyword: Token, name: Token, iterable: Expr, body: Stmt) -> Self {
+        return Stmt::ForInStmt {
+            keyword,
+            name,
+            iterable: Box::new(iterable),
+            body: Box::new(body),
+        };
+    }
+
     pub fn new_block_stmt(declarations: Vec<Stmt>) -> Self {
         return Stmt::BlockStmt {
             declarations: declarations,
         };
     }
 
-    pub fn new_while_stmt(condition: Expr, body: Stmt) -> Self {
+    pub fn new_while_stmt(condition: Expr, body: Stmt, increment: Option<Expr>) -> Self {
         return Stmt::WhileStmt {
             condition: Box::new(condition),
             body: Box::new(body),
+            increment: increment.map(Box::new),
         };
     }
 
-    pub fn new_function(name: Token, parameters: Vec<Token>, body: Vec<Stmt>) -> Self {
+    pub fn new_function(name: Token, parameters: Vec<Parameter>, body: Vec<Stmt>) -> Self {
         return Stmt::FunctionStmt { name, parameters, body };
     }
 
@@ -95,6 +172,60 @@ impl Stmt {
         };
     }
 
+    pub fn new_class_stmt(name: Token, superclass: Option<Expr>, methods: Vec<Stmt>) -> Self {
+        return Stmt::ClassStmt {
+            name,
+            superclass,
+            methods,
+        };
+    }
+
+    pub fn new_enum_stmt(name: Token, variants: Vec<(Token, Vec<Token>)>) -> Self {
+        return Stmt::EnumStmt { name, variants };
+    }
+
+    pub fn new_break_stmt(keyword: Token) -> Self {
+        return Stmt::BreakStmt { keyword };
+    }
+
+    pub fn new_continue_stmt(keyword: Token) -> Self {
+        return Stmt::ContinueStmt { keyword };
+    }
+
+    pub fn new_match_stmt(keyword: Token, subject: Expr, arms: Vec<MatchArm>) -> Self {
+        return Stmt::MatchStmt {
+            keyword,
+            subject: Box::new(subject),
+            arms,
+        };
+    }
+
+    pub fn new_import_stmt(keyword: Token, path: String, name: Token) -> Self {
+        return Stmt::ImportStmt { keyword, path, name };
+    }
+
+    pub fn new_throw_stmt(keyword: Token, value: Expr) -> Self {
+        return Stmt::ThrowStmt {
+            keyword,
+            value: Box::new(value),
+        };
+    }
+
+    pub fn new_yield_stmt(keyword: Token, value: Expr) -> Self {
+        return Stmt::YieldStmt {
+            keyword,
+            value: Box::new(value),
+        };
+    }
+
+    pub fn new_try_stmt(body: Vec<Stmt>, catch_clause: Option<(Token, Vec<Stmt>)>, finally_body: Option<Vec<Stmt>>) -> Self {
+        return Stmt::TryStmt {
+            body,
+            catch_clause,
+            finally_body,
+        };
+    }
+
     pub fn accept<R>(&self, visitor: &mut dyn StmtVisitor<R>) -> R {
         match self {
             Stmt::ExprStmt { expr } => visitor.visit_expr_stmt(expr),
@@ -105,10 +236,70 @@ impl Stmt {
             } => visitor.visit_if_stmt(condition, then_branch, else_branch),
             Stmt::PrintStmt { expr } => visitor.visit_print_stmt(expr),
             Stmt::VarStmt { name, initializer } => visitor.visit_var_stmt(name, initializer),
+            Stmt::ConstStmt { name, initializer } => visitor.visit_const_stmt(name, initializer),
             Stmt::BlockStmt { declarations } => visitor.visit_block_stmt(declarations),
-            Stmt::WhileStmt { condition, body } => visitor.visit_while_stmt(condition, body),
+            Stmt::ForInStmt {
+                keyword,
+                name,
+                iterable,
+                body,
+            } => visitor.visit_for_in_stmt(keyword, name, iterable, body),
+            Stmt::WhileStmt {
+                condition,
+                body,
+                increment,
+            } => visitor.visit_while_stmt(condition, body, increment),
             Stmt::FunctionStmt { name, parameters, body } => visitor.visit_function_stmt(name, parameters, body),
             Stmt::ReturnStmt { keyword, value } => visitor.visit_return_stmt(keyword, value),
+            Stmt::ClassStmt {
+                name,
+                superclass,
+                methods,
+            } => visitor.visit_class_stmt(name, superclass, methods),
+            Stmt::EnumStmt { name, variants } => visitor.visit_enum_stmt(name, variants),
+            Stmt::BreakStmt { keyword } => visitor.visit_break_stmt(keyword),
+            Stmt::ContinueStmt { keyword } => visitor.visit_continue_stmt(keyword),
+            Stmt::MatchStmt { keyword, subject, arms } => visitor.visit_match_stmt(keyword, subject, arms),
+            Stmt::ImportStmt { keyword, path, name } => visitor.visit_import_stmt(keyword, path, name),
+            Stmt::ThrowStmt { keyword, value } => visitor.visit_throw_stmt(keyword, value),
+            Stmt::TryStmt {
+                body,
+                catch_clause,
+                finally_body,
+            } => visitor.visit_try_stmt(body, catch_clause, finally_body),
+            Stmt::YieldStmt { keyword, value } => visitor.visit_yield_stmt(keyword, value),
+        }
+    }
+
+    /// Whether running the statement can reach a `yield` of the enclosing function, which makes that function
+    /// a generator. Nested function declarations are left out, their yields belong to them.
+    pub fn contains_yield(&self) -> bool {
+        match self {
+            Stmt::YieldStmt { .. } => return true,
+            Stmt::BlockStmt { declarations } => return declarations.iter().any(Stmt::contains_yield),
+            Stmt::IfStmt {
+                then_branch,
+                else_branch,
+                ..
+            } => {
+                return then_branch.contains_yield() || else_branch.as_ref().as_ref().is_some_and(Stmt::contains_yield);
+            }
+            Stmt::WhileStmt { body, .. } | Stmt::ForInStmt { body, .. } => return body.contains_yield(),
+            Stmt::MatchStmt { arms, .. } => return arms.iter().any(|arm| arm.body.contains_yield()),
+            Stmt::TryStmt {
+                body,
+                catch_clause,
+                finally_body,
+            } => {
+                return body.iter().any(Stmt::contains_yield)
+                    || catch_clause
+                        .as_ref()
+                        .is_some_and(|(_, handler)| handler.iter().any(Stmt::contains_yield))
+                    || finally_body
+                        .as_ref()
+                        .is_some_and(|finally_body| finally_body.iter().any(Stmt::contains_yield));
+            }
+            _ => return false,
         }
     }
 }
@@ -147,12 +338,34 @@ impl fmt::Display for Stmt {
                     }
                 )
             }
+            Stmt::ConstStmt { name, initializer } => {
+                writeln!(f, "ConstStmt(name: {}, initializer: {})", name, initializer)
+            }
             Stmt::BlockStmt { declarations } => {
                 let decls: Vec<String> = declarations.iter().map(|stmt| format!("{}", stmt)).collect();
                 writeln!(f, "BlockStmt([{}])", decls.join(", "))
             }
-            Stmt::WhileStmt { condition, body } => {
-                writeln!(f, "WhileStmt(condition: {}, body: {})", condition, body)
+            Stmt::ForInStmt {
+                keyword: _,
+                name,
+                iterable,
+                body,
+            } => writeln!(f, "ForInStmt(name: {}, iterable: {}, body: {})", name, iterable, body),
+            Stmt::WhileStmt {
+                condition,
+                body,
+                increment,
+            } => {
+                writeln!(
+                    f,
+                    "WhileStmt(condition: {}, body: {}, increment: {})",
+                    condition,
+                    body,
+                    match increment {
+                        Some(expr) => format!("{}", expr),
+                        None => "None".to_string(),
+                    }
+                )
             }
             Stmt::FunctionStmt { name, parameters, body } => {
                 let params: Vec<String> = parameters.iter().map(|param| format!("{}", param)).collect();
@@ -168,6 +381,70 @@ impl fmt::Display for Stmt {
             Stmt::ReturnStmt { keyword, value } => {
                 writeln!(f, "ReturnStmt(keyword: {}, value: {})", keyword, value)
             }
+            Stmt::ClassStmt {
+                name,
+                superclass,
+                methods,
+            } => {
+                let method_stmts: Vec<String> = methods.iter().map(|stmt| format!("{}", stmt)).collect();
+                writeln!(
+                    f,
+                    "ClassStmt(name: {}, superclass: {}, methods: [{}])",
+                    name,
+                    match superclass {
+                        Some(expr) => format!("{}", expr),
+                        None => "None".to_string(),
+                    },
+                    method_stmts.join(", ")
+                )
+            }
+            Stmt::EnumStmt { name, variants } => {
+                let variants: Vec<String> = variants
+                    .iter()
+                    .map(|(variant, fields)| {
+                        let fields: Vec<&str> = fields.iter().map(|field| field.lexeme.as_str()).collect();
+                        format!("{}({})", variant.lexeme, fields.join(", "))
+                    })
+                    .collect();
+                writeln!(f, "EnumStmt(name: {}, variants: [{}])", name, variants.join(", "))
+            }
+            Stmt::BreakStmt { keyword: _ } => writeln!(f, "BreakStmt"),
+            Stmt::ContinueStmt { keyword: _ } => writeln!(f, "ContinueStmt"),
+            Stmt::MatchStmt {
+                keyword: _,
+                subject,
+                arms,
+            } => {
+                let arms: Vec<String> = arms.iter().map(|arm| format!("{}", arm)).collect();
+                writeln!(f, "MatchStmt(subject: {}, arms: [{}])", subject, arms.join(", "))
+            }
+            Stmt::ImportStmt { keyword: _, path, name } => writeln!(f, "ImportStmt(path: {}, name: {})", path, name),
+            Stmt::ThrowStmt { keyword: _, value } => writeln!(f, "ThrowStmt({})", value),
+            Stmt::YieldStmt { keyword: _, value } => writeln!(f, "YieldStmt({})", value),
+            Stmt::TryStmt {
+                body,
+                catch_clause,
+                finally_body,
+            } => {
+                let format_block = |statements: &Vec<Stmt>| -> String {
+                    let stmts: Vec<String> = statements.iter().map(|stmt| format!("{}", stmt)).collect();
+                    return format!("[{}]", stmts.join(", "));
+                };
+
+                writeln!(
+                    f,
+                    "TryStmt(body: {}, catch: {}, finally: {})",
+                    format_block(body),
+                    match catch_clause {
+                        Some((name, handler)) => format!("{} {}", name, format_block(handler)),
+                        None => "None".to_string(),
+                    },
+                    match finally_body {
+                        Some(statements) => format_block(statements),
+                        None => "None".to_string(),
+                    }
+                )
+            }
         }
     }
 }
@@ -1,9 +1,15 @@
 use std::fmt::Display;
 
-use crate::{interpreter::Interpretable, token::Token};
+use crate::{
+    interpreter::{lox_callable::TailCall, Interpretable},
+    token::Token,
+};
 
-fn report(line: u32, location: &str, message: &str) {
-    eprintln!("line {line} Error{location}: {message}");
+fn report(file: &Option<String>, line: u32, location: &str, message: &str) {
+    match file {
+        Some(file) => eprintln!("{file}: line {line} Error{location}: {message}"),
+        None => eprintln!("line {line} Error{location}: {message}"),
+    }
 }
 pub trait ProcessingErrorHandler {
     fn scanning_error(&mut self, _line: u32, _message: &str) {
@@ -21,22 +27,34 @@ pub trait ProcessingErrorHandler {
 
 pub struct ErrorHandler {
     pub had_error: bool,
+    // Named in reports when the source isn't the script being run, like an imported module
+    file: Option<String>,
 }
 
 impl ErrorHandler {
     pub fn new() -> Self {
-        return Self { had_error: false };
+        return Self {
+            had_error: false,
+            file: None,
+        };
+    }
+
+    pub fn for_file(file: &str) -> Self {
+        return Self {
+            had_error: false,
+            file: Some(file.to_string()),
+        };
     }
 }
 
 impl ProcessingErrorHandler for ErrorHandler {
     fn scanning_error(&mut self, line: u32, message: &str) {
-        report(line, "", message);
+        report(&self.file, line, "", message);
         self.had_error = true;
     }
 
     fn parsing_error(&mut self, line: u32, location: &str, message: &str) {
-        report(line, location, message);
+        report(&self.file, line, location, message);
         self.had_error = true;
     }
 
@@ -51,6 +69,12 @@ pub enum RuntimeEvent {
     ParseError(String),
     InterpreterError(Token, String),
     Return(Interpretable),
+    Break,
+    Continue,
+    // Boxed so the thrown value doesn't grow every Result the interpreter passes around
+    Throw(Token, Box<Interpretable>),
+    // Unwinds the returning function before its callee runs, boxed for the same reason as thrown values
+    TailCall(Box<TailCall>),
 }
 
 impl RuntimeEvent {
@@ -65,6 +89,10 @@ impl RuntimeEvent {
     pub fn new_return(value: Interpretable) -> Self {
         return RuntimeEvent::Return(value);
     }
+
+    pub fn new_throw(keyword: Token, value: Interpretable) -> Self {
+        return RuntimeEvent::Throw(keyword, Box::new(value));
+    }
 }
 
 impl Display for RuntimeEvent {
@@ -75,6 +103,12 @@ impl Display for RuntimeEvent {
                 write!(f, "[line {}] InterpretError: {} ", token.line, msg)
             }
             RuntimeEvent::Return(v) => write!(f, "Return value: {}", v),
+            RuntimeEvent::Break => write!(f, "Break"),
+            RuntimeEvent::Continue => write!(f, "Continue"),
+            RuntimeEvent::Throw(keyword, value) => {
+                write!(f, "[line {}] InterpretError: Uncaught exception {} ", keyword.line, value)
+            }
+            RuntimeEvent::TailCall(tail_call) => write!(f, "Tail call at line {}", tail_call.paren.line),
         }
     }
 }
@@ -1,5 +1,5 @@
 use std::cell::RefCell;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fmt::Display;
 use std::rc::Rc;
 
@@ -43,6 +43,10 @@ impl Environment {
     }
 
     pub fn assign(&mut self, name: &Token, value: &Interpretable) -> Result<Interpretable, RuntimeEvent> {
+        if self.values.is_constant(name) {
+            return Err(constant_assignment_error(name));
+        }
+
         if let Some(v) = self.values.assign(name, value) {
             // Found in the current environment
             return Ok(v.clone());
@@ -58,19 +62,83 @@ impl Environment {
         ))
     }
 
+    pub fn get_at(&self, distance: usize, name: &Token) -> Result<Interpretable, RuntimeEvent> {
+        if distance == 0 {
+            if let Some(v) = self.values.get(name) {
+                return Ok(v.clone());
+            }
+        } else if let Some(e) = &self.enclosing {
+            return e.borrow().get_at(distance - 1, name);
+        }
+
+        Err(RuntimeEvent::interpreter_error(
+            name.clone(),
+            &format!("Undefined variable '{}'.", name.lexeme),
+        ))
+    }
+
+    pub fn assign_at(&mut self, distance: usize, name: &Token, value: &Interpretable) -> Result<Interpretable, RuntimeEvent> {
+        if distance == 0 {
+            if self.values.is_constant(name) {
+                return Err(constant_assignment_error(name));
+            }
+
+            if let Some(v) = self.values.assign(name, value) {
+                return Ok(v.clone());
+            }
+        } else if let Some(e) = &self.enclosing {
+            return e.borrow_mut().assign_at(distance - 1, name, value);
+        }
+
+        Err(RuntimeEvent::interpreter_error(
+            name.clone(),
+            &format!("Undefined variable '{}'.", name.lexeme),
+        ))
+    }
+
     pub fn define(&mut self, name: String, value: Interpretable) -> Option<Interpretable> {
         return self.values.define(name, value);
     }
+
+    pub fn define_constant(&mut self, name: String, value: Interpretable) -> Option<Interpretable> {
+        return self.values.define_constant(name, value);
+    }
+
+    pub fn define_native(&mut self, name: String, value: Interpretable) -> Option<Interpretable> {
+        return self.values.define_native(name, value);
+    }
+}
+
+fn constant_assignment_error(name: &Token) -> RuntimeEvent {
+    return RuntimeEvent::interpreter_error(
+        name.clone(),
+        &format!("Can't assign to constant '{}'.", name.lexeme),
+    );
 }
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct ValueMap {
     values: HashMap<String, Interpretable>,
+    constants: HashSet<String>,
+    // Natives are constants too, but a declaration of the same name replaces them
+    natives: HashSet<String>,
 }
 
 impl ValueMap {
     pub fn new() -> Self {
-        return ValueMap { values: HashMap::new() };
+        return ValueMap {
+            values: HashMap::new(),
+            constants: HashSet::new(),
+            natives: HashSet::new(),
+        };
+    }
+
+    pub fn is_constant(&self, name: &Token) -> bool {
+        return self.constants.contains(&name.lexeme);
+    }
+
+    pub fn is_native(&self, name: &Token) -> bool {
+        return self.natives.contains(&name.lexeme);
     }
 
     pub fn get(&self, name: &Token) -> Option<&Interpretable> {
@@ -86,6 +154,22 @@ impl ValueMap {
     }
 
     pub fn define(&mut self, name: String, value: Interpretable) -> Option<Interpretable> {
+        if self.natives.remove(&name) {
+            self.constants.remove(&name);
+        }
+
+        return self.values.insert(name, value);
+    }
+
+    pub fn define_constant(&mut self, name: String, value: Interpretable) -> Option<Interpretable> {
+        self.natives.remove(&name);
+        self.constants.insert(name.clone());
+        return self.values.insert(name, value);
+    }
+
+    pub fn define_native(&mut self, name: String, value: Interpretable) -> Option<Interpretable> {
+        self.natives.insert(name.clone());
+        self.constants.insert(name.clone());
         return self.values.insert(name, value);
     }
 }
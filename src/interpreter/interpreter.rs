@@ -1,34 +1,105 @@
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
 use std::fmt::Display;
+use std::fs;
 use std::io::Write;
+use std::path::{Path, PathBuf};
 use std::rc::Rc;
 
-use crate::ast::{Expr, ExprVisitor, Stmt, StmtVisitor};
+use crate::ast::{Expr, ExprVisitor, MatchArm, Parameter, Pattern, Stmt, StmtVisitor};
 use crate::error::{ErrorHandler, ProcessingErrorHandler, RuntimeEvent};
 use crate::interpreter::lox_callable::LoxCallable;
+use crate::parser::Parser;
+use crate::resolver::Resolver;
+use crate::scanner::Scanner;
 use crate::token::types::{Literal, TokenKind};
 use crate::token::Token;
 
-use super::lox_callable::{native_clock_call, LoxFunction};
+use super::lox_callable::{check_arity, FunctionBody, LoxFunction, TailCall};
+use super::lox_class::{LoxClass, LoxInstance};
+use super::lox_enum::{LoxEnum, LoxEnumValue, LoxVariant};
+use super::lox_generator::LoxGenerator;
+use super::lox_iterator::LoxIterator;
+use super::lox_map::{LoxMap, MapKey};
+use super::lox_module::LoxModule;
+use super::lox_range::LoxRange;
+use super::natives::{self, list_index};
+use super::recursion_guard;
 use super::Environment;
 
-#[derive(Clone, Debug, PartialEq)]
+/// Calls that can be nested before a "Stack overflow." error, unless set otherwise.
+pub const DEFAULT_MAX_CALL_DEPTH: usize = 1000;
+
+// Native stack kept free below the deepest statement or expression, for the frames run before the next check
+const STACK_RED_ZONE: usize = 256 * 1024;
+
+// Evaluated positional and named arguments of a call
+type Arguments = (Vec<Interpretable>, Vec<(Token, Interpretable)>);
+
+#[derive(Clone, Debug)]
 pub enum Interpretable {
     String(String),
     Number(f64),
     Boolean(bool),
     Callable(LoxFunction),
+    Class(Rc<LoxClass>),
+    Instance(Rc<RefCell<LoxInstance>>),
+    List(Rc<RefCell<Vec<Interpretable>>>),
+    Map(Rc<RefCell<LoxMap>>),
+    Module(Rc<LoxModule>),
+    Enum(Rc<LoxEnum>),
+    Variant(Rc<LoxVariant>),
+    EnumValue(Rc<LoxEnumValue>),
+    Range(LoxRange),
+    Generator(Rc<LoxGenerator>),
     Nil,
 }
 
 impl Interpretable {
-    fn is_truthy(&self) -> bool {
+    pub fn is_truthy(&self) -> bool {
         match self {
             Interpretable::Boolean(b) => return *b,
             Interpretable::Nil => return false,
             _ => return true,
         }
     }
+
+    // Text used when a value is embedded in a string, same as print but without quoting strings
+    fn stringify(&self) -> String {
+        match self {
+            Interpretable::String(s) => return s.clone(),
+            _ => return self.to_string(),
+        }
+    }
+}
+
+impl PartialEq for Interpretable {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Interpretable::String(a), Interpretable::String(b)) => return a == b,
+            (Interpretable::Number(a), Interpretable::Number(b)) => return a == b,
+            (Interpretable::Boolean(a), Interpretable::Boolean(b)) => return a == b,
+            (Interpretable::Callable(a), Interpretable::Callable(b)) => return a == b,
+            (Interpretable::Class(a), Interpretable::Class(b)) => return a == b,
+            (Interpretable::Instance(a), Interpretable::Instance(b)) => return a == b,
+            (Interpretable::List(a), Interpretable::List(b)) => {
+                return Rc::ptr_eq(a, b)
+                    || recursion_guard::comparing(Rc::as_ptr(a) as *const (), Rc::as_ptr(b) as *const (), || a == b);
+            }
+            (Interpretable::Map(a), Interpretable::Map(b)) => {
+                return Rc::ptr_eq(a, b)
+                    || recursion_guard::comparing(Rc::as_ptr(a) as *const (), Rc::as_ptr(b) as *const (), || a == b);
+            }
+            (Interpretable::Module(a), Interpretable::Module(b)) => return a == b,
+            (Interpretable::Enum(a), Interpretable::Enum(b)) => return a == b,
+            (Interpretable::Variant(a), Interpretable::Variant(b)) => return a == b,
+            (Interpretable::EnumValue(a), Interpretable::EnumValue(b)) => return a == b,
+            (Interpretable::Range(a), Interpretable::Range(b)) => return a == b,
+            (Interpretable::Generator(a), Interpretable::Generator(b)) => return a == b,
+            (Interpretable::Nil, Interpretable::Nil) => return true,
+            _ => return false,
+        }
+    }
 }
 
 impl Display for Interpretable {
@@ -38,53 +109,119 @@ impl Display for Interpretable {
             Interpretable::Number(n) => write!(f, "{}", n),
             Interpretable::Boolean(b) => write!(f, "{}", b),
             Interpretable::Callable(c) => write!(f, "{}", c),
+            Interpretable::Class(c) => write!(f, "{}", c),
+            Interpretable::Instance(i) => write!(f, "{}", i.borrow()),
+            Interpretable::List(l) => {
+                // A list met again while printing itself shows as [...]
+                let printed = recursion_guard::printing(Rc::as_ptr(l) as *const (), || write_list(f, &l.borrow()));
+                printed.unwrap_or_else(|| write!(f, "[...]"))
+            }
+            Interpretable::Map(m) => {
+                // A map met again while printing itself shows as {...}
+                let printed = recursion_guard::printing(Rc::as_ptr(m) as *const (), || write!(f, "{}", m.borrow()));
+                printed.unwrap_or_else(|| write!(f, "{{...}}"))
+            }
+            Interpretable::Module(m) => write!(f, "{}", m),
+            Interpretable::Enum(e) => write!(f, "{}", e),
+            Interpretable::Variant(v) => write!(f, "{}", v),
+            Interpretable::EnumValue(v) => write!(f, "{}", v),
+            Interpretable::Range(r) => write!(f, "{}", r),
+            Interpretable::Generator(g) => write!(f, "{}", g),
             Interpretable::Nil => write!(f, "nil"),
         }
     }
 }
 
+fn write_list(f: &mut std::fmt::Formatter<'_>, list: &[Interpretable]) -> std::fmt::Result {
+    write!(f, "[")?;
+    for (i, element) in list.iter().enumerate() {
+        if i > 0 {
+            write!(f, ", ")?;
+        }
+        write!(f, "{}", element)?;
+    }
+    write!(f, "]")
+}
+
 pub struct Interpreter {
-    pub globals: Rc<RefCell<Environment>>,
     pub environment: Rc<RefCell<Environment>>,
     error_handler: ErrorHandler,
+    runtime_error_class: Rc<LoxClass>,
+    // Files currently being run, the script first and then each module in the middle of being imported
+    module_stack: Vec<PathBuf>,
+    // Each module runs once, `None` records one whose run failed
+    modules: HashMap<PathBuf, Option<Rc<LoxModule>>>,
+    // Calls currently running by the token that made them, tail calls reuse the depth of the call they replace
+    calls: Vec<Token>,
+    max_call_depth: usize,
+    // Native stack of the thread running the interpreter, nesting can exhaust it before the calls reach their limit
+    stack_start: usize,
+    stack_size: Option<usize>,
 }
 
 impl Interpreter {
     pub fn new() -> Self {
-        let globals = Rc::new(RefCell::new(Environment::new()));
-        let environment = Rc::clone(&globals);
+        let environment = Rc::new(RefCell::new(Environment::new()));
         let error_handler = ErrorHandler::new();
+        let runtime_error_class = Rc::new(LoxClass::new(
+            &Token::new(TokenKind::Identifier, "RuntimeError", None, 0),
+            None,
+            HashMap::new(),
+        ));
+
+        define_natives(&mut environment.borrow_mut());
 
-        let instance = Interpreter {
-            globals,
+        return Interpreter {
             environment,
             error_handler,
+            runtime_error_class,
+            module_stack: Vec::new(),
+            modules: HashMap::new(),
+            calls: Vec::new(),
+            max_call_depth: DEFAULT_MAX_CALL_DEPTH,
+            stack_start: 0,
+            stack_size: None,
         };
+    }
 
-        let clock_callable = LoxFunction::new_native_function(0, native_clock_call);
+    /// Limits how deep calls can nest, deeper ones fail with a "Stack overflow." runtime error.
+    pub fn set_max_call_depth(&mut self, max_call_depth: usize) {
+        self.max_call_depth = max_call_depth;
+    }
 
-        instance
-            .globals
-            .borrow_mut()
-            .define(String::from("clock"), Interpretable::Callable(clock_callable));
+    /// Bounds the native stack the interpreter may use, counted from where this is called on its thread.
+    pub fn set_stack_size(&mut self, stack_size: usize) {
+        self.stack_start = stack_address();
+        self.stack_size = Some(stack_size);
+    }
 
-        return instance;
+    /// Makes imports resolve relative to the directory of the script, instead of the working directory.
+    pub fn set_script_path(&mut self, script_path: &Path) {
+        let script_path = script_path.canonicalize().unwrap_or(script_path.to_path_buf());
+        self.module_stack = vec![script_path];
     }
 
     fn evaluate(&mut self, expression: &Expr) -> Result<Interpretable, RuntimeEvent> {
+        self.check_native_stack()?;
         return expression.accept(self);
     }
 
     fn execute(&mut self, statement: &Stmt) -> Result<Interpretable, RuntimeEvent> {
+        self.check_native_stack()?;
         return statement.accept(self);
     }
 
     pub fn execute_block(&mut self, statements: &Vec<Stmt>, enclosing:Environment) -> Result<Interpretable, RuntimeEvent> {
+        return self.execute_in(statements, Rc::new(RefCell::new(enclosing)));
+    }
+
+    // Same as execute_block, for an environment the caller keeps a handle on
+    pub fn execute_in(&mut self, statements: &[Stmt], environment: Rc<RefCell<Environment>>) -> Result<Interpretable, RuntimeEvent> {
         let mut result = Ok(Interpretable::Nil);
 
         let previous = self.environment.clone();
 
-        self.environment = Rc::new(RefCell::new(enclosing));
+        self.environment = environment;
         for statement in statements {
             result = self.execute(statement);
 
@@ -99,24 +236,19 @@ impl Interpreter {
         return result;
     }
 
-    pub fn interpret(&mut self, statements: Vec<Stmt>) {
-        for statement in statements {
-            let result = self.execute(&statement);
+    pub fn evaluate_in(&mut self, expression: &Expr, environment: Rc<RefCell<Environment>>) -> Result<Interpretable, RuntimeEvent> {
+        let previous = std::mem::replace(&mut self.environment, environment);
+        let result = self.evaluate(expression);
+        self.environment = previous;
 
-            if result.is_err() {
-                self.error_handler
-                    .runtime_error(result.err().expect("Invalid interpreter error state"));
-            }
-        }
+        return result;
     }
-}
-
-impl ExprVisitor<Result<Interpretable, RuntimeEvent>> for Interpreter {
-    fn visit_binary_expr(&mut self, left: &Expr, operator: &Token, right: &Expr) -> Result<Interpretable, RuntimeEvent> {
-        let l_eval = self.evaluate(left)?;
-        let r_eval = self.evaluate(right)?;
 
+    fn binary_operation(&self, operator: &Token, l_eval: Interpretable, r_eval: Interpretable) -> Result<Interpretable, RuntimeEvent> {
         match (&operator.kind, l_eval, r_eval) {
+            // Both sides were already evaluated in order, the comma only keeps the right one
+            (TokenKind::Comma, _, right) => return Ok(right),
+
             (TokenKind::Minus, Interpretable::Number(left_number), Interpretable::Number(right_number)) => {
                 return Ok(Interpretable::Number(left_number - right_number));
             }
@@ -129,6 +261,20 @@ impl ExprVisitor<Result<Interpretable, RuntimeEvent>> for Interpreter {
                 return Ok(Interpretable::Number(left_number / right_number));
             }
 
+            // Remainder and floor division both round towards negative infinity, so that
+            // a == (a ~/ b) * b + a % b holds and the remainder takes the sign of the divisor
+            (TokenKind::Percent, Interpretable::Number(left_number), Interpretable::Number(right_number)) => {
+                return Ok(Interpretable::Number(left_number - right_number * (left_number / right_number).floor()));
+            }
+
+            (TokenKind::TildeSlash, Interpretable::Number(left_number), Interpretable::Number(right_number)) => {
+                return Ok(Interpretable::Number((left_number / right_number).floor()));
+            }
+
+            (TokenKind::StarStar, Interpretable::Number(left_number), Interpretable::Number(right_number)) => {
+                return Ok(Interpretable::Number(left_number.powf(right_number)));
+            }
+
             (TokenKind::Plus, Interpretable::Number(left_number), Interpretable::Number(right_number)) => {
                 return Ok(Interpretable::Number(left_number + right_number));
             }
@@ -161,33 +307,454 @@ impl ExprVisitor<Result<Interpretable, RuntimeEvent>> for Interpreter {
                 return Ok(Interpretable::Boolean(l == r));
             }
 
-            (_, _, _) => {
+            (_, l, r) => {
                 return Err(RuntimeEvent::interpreter_error(
                     operator.clone(),
-                    &format!("Invalid operands {} and {} to operator '{}'", left, right, operator.lexeme),
+                    &format!("Invalid operands {} and {} to operator '{}'", l, r, operator.lexeme),
                 ));
             }
         }
     }
 
-    fn visit_call_expr(&mut self, callee: &Expr, paren: &Token, arguments: &Vec<Expr>) -> Result<Interpretable, RuntimeEvent> {
-        let callee_eval = self.evaluate(callee)?;
+    fn index_get(&self, object: &Interpretable, bracket: &Token, index: &Interpretable) -> Result<Interpretable, RuntimeEvent> {
+        match object {
+            Interpretable::List(list) => {
+                let position = list_index(bracket, index, list.borrow().len())?;
+                return Ok(list.borrow()[position].clone());
+            }
+            Interpretable::Map(map) => {
+                let key = MapKey::from(bracket, index)?;
+
+                match map.borrow().get(&key) {
+                    Some(value) => return Ok(value.clone()),
+                    None => {
+                        return Err(RuntimeEvent::interpreter_error(
+                            bracket.clone(),
+                            &format!("Key {} not found in map.", index),
+                        ))
+                    }
+                }
+            }
+            _ => return Err(RuntimeEvent::interpreter_error(bracket.clone(), "Only lists and maps can be indexed.")),
+        }
+    }
 
-        let mut args_eval: Vec<Interpretable> = Vec::new();
-        for argument in arguments {
-            args_eval.push(self.evaluate(argument)?);
+    fn index_set(&self, object: &Interpretable, bracket: &Token, index: &Interpretable, value: Interpretable) -> Result<Interpretable, RuntimeEvent> {
+        match object {
+            Interpretable::List(list) => {
+                let position = list_index(bracket, index, list.borrow().len())?;
+                list.borrow_mut()[position] = value.clone();
+
+                return Ok(value);
+            }
+            Interpretable::Map(map) => {
+                let key = MapKey::from(bracket, index)?;
+                map.borrow_mut().insert(key, value.clone());
+
+                return Ok(value);
+            }
+            _ => return Err(RuntimeEvent::interpreter_error(bracket.clone(), "Only lists and maps can be indexed.")),
         }
+    }
 
-        match callee_eval {
-            Interpretable::Callable(function) => {
-                if arguments.len() != function.arity() {
-                    return Err(RuntimeEvent::interpreter_error(
-                        paren.clone(),
-                        &format!("Expected {}  arguments, but got {}.", function.arity(), arguments.len()),
-                    ));
+    fn look_up_variable(&self, name: &Token, depth: &Cell<Option<usize>>) -> Result<Interpretable, RuntimeEvent> {
+        match depth.get() {
+            Some(distance) => return self.environment.borrow().get_at(distance, name),
+            None => return self.global_environment().borrow().get(name),
+        }
+    }
+
+    // Unresolved names are globals of the module the running code was declared in, which is the outermost
+    // environment of the current chain rather than the globals of the script
+    fn global_environment(&self) -> Rc<RefCell<Environment>> {
+        let mut environment = Rc::clone(&self.environment);
+
+        loop {
+            let enclosing = environment.borrow().enclosing.clone();
+
+            match enclosing {
+                Some(enclosing) => environment = enclosing,
+                None => return environment,
+            }
+        }
+    }
+
+    fn load_module(&mut self, keyword: &Token, path: &str) -> Result<Rc<LoxModule>, RuntimeEvent> {
+        let directory = match self.module_stack.last() {
+            Some(importer) => importer.parent().map(Path::to_path_buf).unwrap_or_default(),
+            None => PathBuf::new(),
+        };
+
+        let Ok(module_path) = directory.join(path).canonicalize() else {
+            return Err(RuntimeEvent::interpreter_error(
+                keyword.clone(),
+                &format!("Can't find module '{}'.", path),
+            ));
+        };
+
+        match self.modules.get(&module_path) {
+            Some(Some(module)) => return Ok(Rc::clone(module)),
+            Some(None) => {
+                return Err(RuntimeEvent::interpreter_error(
+                    keyword.clone(),
+                    &format!("Module '{}' failed to load.", path),
+                ))
+            }
+            None => {}
+        }
+
+        if let Some(position) = self.module_stack.iter().position(|loading| *loading == module_path) {
+            let chain: Vec<String> = self.module_stack[position..]
+                .iter()
+                .chain(std::iter::once(&module_path))
+                .map(|path| path.display().to_string())
+                .collect();
+
+            return Err(RuntimeEvent::interpreter_error(
+                keyword.clone(),
+                &format!("Import cycle detected: {}.", chain.join(" -> ")),
+            ));
+        }
+
+        let Ok(source) = fs::read_to_string(&module_path) else {
+            return Err(RuntimeEvent::interpreter_error(
+                keyword.clone(),
+                &format!("Can't read module '{}'.", path),
+            ));
+        };
+
+        // Syntax and resolution errors are reported while processing the module, the import only fails
+        let mut error_handler = ErrorHandler::for_file(&module_path.display().to_string());
+        let mut scanner = Scanner::new(&source, &mut error_handler);
+        let tokens = scanner.scan_tokens();
+
+        let mut parser = Parser::new(tokens, &mut error_handler);
+        let statements = parser.parse();
+
+        if let Ok(statements) = &statements {
+            Resolver::new(&mut error_handler).resolve(statements);
+        }
+
+        let statements = match statements {
+            Ok(statements) if !error_handler.had_error => statements,
+            _ => {
+                self.modules.insert(module_path, None);
+                return Err(RuntimeEvent::interpreter_error(
+                    keyword.clone(),
+                    &format!("Module '{}' has errors.", path),
+                ))
+            }
+        };
+
+        let module_globals = Rc::new(RefCell::new(Environment::new()));
+        define_natives(&mut module_globals.borrow_mut());
+
+        let previous = std::mem::replace(&mut self.environment, Rc::clone(&module_globals));
+        self.module_stack.push(module_path.clone());
+
+        let mut result = Ok(Interpretable::Nil);
+        for statement in &statements {
+            result = self.execute(statement);
+
+            if result.is_err() {
+                break;
+            }
+        }
+
+        self.module_stack.pop();
+        self.environment = previous;
+
+        // A failed module isn't run again by later imports, they fail as well
+        if let Err(error) = result {
+            self.modules.insert(module_path, None);
+            return Err(error);
+        }
+
+        let name = module_path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let module = Rc::new(LoxModule::new(&name, module_globals));
+        self.modules.insert(module_path, Some(Rc::clone(&module)));
+
+        return Ok(module);
+    }
+
+    // Declarations may shadow a constant of an enclosing scope, but not replace one in the same scope. Natives
+    // are the exception, a script can declare its own `values` or `sort` in their place
+    fn define_variable(&self, name: &Token, value: Interpretable, is_constant: bool) -> Result<(), RuntimeEvent> {
+        let mut environment = self.environment.borrow_mut();
+
+        if environment.values.is_constant(name) && !environment.values.is_native(name) {
+            return Err(RuntimeEvent::interpreter_error(
+                name.clone(),
+                &format!("Can't redefine constant '{}'.", name.lexeme),
+            ));
+        }
+
+        if is_constant {
+            environment.define_constant(name.lexeme.clone(), value);
+        } else {
+            environment.define(name.lexeme.clone(), value);
+        }
+
+        return Ok(());
+    }
+
+    fn assign_variable(&self, name: &Token, depth: &Cell<Option<usize>>, value: &Interpretable) -> Result<Interpretable, RuntimeEvent> {
+        match depth.get() {
+            Some(distance) => return self.environment.borrow_mut().assign_at(distance, name, value),
+            None => return self.global_environment().borrow_mut().assign(name, value),
+        }
+    }
+
+    // Reads the current value of an assignable target, passes it to `update` and stores the first value it
+    // returns. The object and index of the target are evaluated only once, and the second value returned by
+    // `update` becomes the result of the whole expression
+    fn update_target<F>(&mut self, target: &Expr, update: F) -> Result<Interpretable, RuntimeEvent>
+    where
+        F: FnOnce(&mut Self, Interpretable) -> Result<(Interpretable, Interpretable), RuntimeEvent>,
+    {
+        match target {
+            Expr::Variable { name, depth } => {
+                let current = self.look_up_variable(name, depth)?;
+                let (value, result) = update(self, current)?;
+                self.assign_variable(name, depth, &value)?;
+
+                return Ok(result);
+            }
+            Expr::Get { object, name } => {
+                let instance = match self.evaluate(object)? {
+                    Interpretable::Instance(instance) => instance,
+                    _ => return Err(RuntimeEvent::interpreter_error(name.clone(), "Only instances have fields.")),
+                };
+
+                let current = LoxInstance::get(&instance, name)?;
+                let (value, result) = update(self, current)?;
+                instance.borrow_mut().set(name, value);
+
+                return Ok(result);
+            }
+            Expr::Index { object, bracket, index } => {
+                let object = self.evaluate(object)?;
+                let index = self.evaluate(index)?;
+
+                let current = self.index_get(&object, bracket, &index)?;
+                let (value, result) = update(self, current)?;
+                self.index_set(&object, bracket, &index, value)?;
+
+                return Ok(result);
+            }
+            _ => unreachable!("The parser only accepts variables, properties and indexes as assignment targets."),
+        }
+    }
+
+    // Turns an error raised by the interpreter itself into a value a catch clause can bind, an instance of
+    // RuntimeError with the message and the line it was raised at
+    fn runtime_error_value(&self, token: &Token, message: &str) -> Interpretable {
+        let mut error = LoxInstance::new(self.runtime_error_class.clone());
+        error.set(
+            &Token::new(TokenKind::Identifier, "message", None, token.line),
+            Interpretable::String(message.to_string()),
+        );
+        error.set(
+            &Token::new(TokenKind::Identifier, "line", None, token.line),
+            Interpretable::Number(token.line as f64),
+        );
+
+        return Interpretable::Instance(Rc::new(RefCell::new(error)));
+    }
+
+    /// Value a catch clause binds for `event`, or `None` when the event isn't caught. Only thrown values and
+    /// runtime errors are, return, break and continue pass through.
+    pub fn caught_value(&self, event: &RuntimeEvent) -> Option<Interpretable> {
+        match event {
+            RuntimeEvent::Throw(_, value) => return Some(*value.clone()),
+            RuntimeEvent::InterpreterError(token, message) => return Some(self.runtime_error_value(token, message)),
+            _ => return None,
+        }
+    }
+
+    /// Picks the first arm of a `match` whose pattern and guard accept the subject, evaluated in `environment`.
+    /// Also returns the environment the body of the arm runs in, which holds the fields bound by its pattern.
+    pub fn select_match_arm(
+        &mut self,
+        keyword: &Token,
+        subject: &Expr,
+        arms: &[MatchArm],
+        environment: Rc<RefCell<Environment>>,
+    ) -> Result<(usize, Rc<RefCell<Environment>>), RuntimeEvent> {
+        let previous = std::mem::replace(&mut self.environment, environment);
+        let result = self.find_match_arm(keyword, subject, arms);
+        self.environment = previous;
+
+        return result;
+    }
+
+    fn find_match_arm(&mut self, keyword: &Token, subject: &Expr, arms: &[MatchArm]) -> Result<(usize, Rc<RefCell<Environment>>), RuntimeEvent> {
+        let value = self.evaluate(subject)?;
+
+        for (index, arm) in arms.iter().enumerate() {
+            let mut matched = false;
+            for pattern in &arm.patterns {
+                if self.matches_pattern(pattern, &value)? {
+                    matched = true;
+                    break;
                 }
+            }
 
-                return function.call(self, &mut args_eval);
+            if !matched {
+                continue;
+            }
+
+            // Bound fields live in their own scope around the guard and the body
+            let mut environment = self.environment.clone();
+            if let (Some(bindings), Interpretable::EnumValue(enum_value)) = (arm.bindings(), &value) {
+                let mut arm_environment = Environment::from(environment);
+                for (name, field) in bindings.iter().zip(enum_value.values.iter()) {
+                    if name.lexeme != "_" {
+                        arm_environment.define(name.lexeme.clone(), field.clone());
+                    }
+                }
+
+                environment = Rc::new(RefCell::new(arm_environment));
+            }
+
+            // The guard is only evaluated once a pattern matched
+            if let Some(guard) = &arm.guard {
+                if !self.evaluate_in(guard, environment.clone())?.is_truthy() {
+                    continue;
+                }
+            }
+
+            return Ok((index, environment));
+        }
+
+        return Err(RuntimeEvent::interpreter_error(
+            keyword.clone(),
+            &format!("No match arm matches value {}.", value),
+        ));
+    }
+
+    fn matches_pattern(&mut self, pattern: &Pattern, value: &Interpretable) -> Result<bool, RuntimeEvent> {
+        let Pattern::Variant {
+            enum_name,
+            variant,
+            fields,
+        } = pattern
+        else {
+            return Ok(matches_literal_pattern(pattern, value));
+        };
+
+        let expected = match self.evaluate(enum_name)? {
+            Interpretable::Enum(lox_enum) => lox_enum.find_variant(variant)?,
+            _ => return Err(RuntimeEvent::interpreter_error(variant.clone(), "Only enums have variants.")),
+        };
+
+        if let Some(fields) = fields {
+            if fields.len() != expected.fields.len() {
+                return Err(RuntimeEvent::interpreter_error(
+                    variant.clone(),
+                    &format!(
+                        "Expected {} fields in pattern for {}, but got {}.",
+                        expected.fields.len(),
+                        variant.lexeme,
+                        fields.len()
+                    ),
+                ));
+            }
+        }
+
+        match value {
+            Interpretable::EnumValue(enum_value) => return Ok(Rc::ptr_eq(&enum_value.variant, &expected)),
+            _ => return Ok(false),
+        }
+    }
+
+    fn call(
+        &mut self,
+        callee: &impl LoxCallable,
+        paren: &Token,
+        arguments: &mut Vec<Interpretable>,
+        named_arguments: Vec<(Token, Interpretable)>,
+    ) -> Result<Interpretable, RuntimeEvent> {
+        // Which parameters named arguments fill is only known to the callee, so it checks them itself
+        if !named_arguments.is_empty() {
+            return callee.call_named(self, paren, std::mem::take(arguments), named_arguments);
+        }
+
+        check_arity(callee, paren, arguments.len())?;
+
+        return callee.call(self, paren, arguments);
+    }
+
+    /// Runs `nested`, which goes one call deeper on the native stack. Runaway recursion is reported as a runtime
+    /// error at `token` before it can exhaust the stack.
+    pub fn with_call_depth<T>(
+        &mut self,
+        token: &Token,
+        nested: impl FnOnce(&mut Interpreter) -> Result<T, RuntimeEvent>,
+    ) -> Result<T, RuntimeEvent> {
+        if self.calls.len() >= self.max_call_depth || self.native_stack_exhausted() {
+            return Err(RuntimeEvent::interpreter_error(token.clone(), "Stack overflow."));
+        }
+
+        self.calls.push(token.clone());
+        let result = nested(self);
+        self.calls.pop();
+
+        return result;
+    }
+
+    fn native_stack_exhausted(&self) -> bool {
+        return match self.stack_size {
+            Some(stack_size) => stack_address().abs_diff(self.stack_start) + STACK_RED_ZONE > stack_size,
+            None => false,
+        };
+    }
+
+    // Statements and expressions nested inside a call take native stack as well, the overflow is reported at that call.
+    // Outside of calls nesting is bounded by the parser, which recursed as deep on the same stack.
+    fn check_native_stack(&self) -> Result<(), RuntimeEvent> {
+        if let Some(token) = self.calls.last() {
+            if self.native_stack_exhausted() {
+                return Err(RuntimeEvent::interpreter_error(token.clone(), "Stack overflow."));
+            }
+        }
+
+        return Ok(());
+    }
+
+    pub fn call_value(
+        &mut self,
+        callee: Interpretable,
+        paren: &Token,
+        arguments: Vec<Interpretable>,
+        named_arguments: Vec<(Token, Interpretable)>,
+    ) -> Result<Interpretable, RuntimeEvent> {
+        return self.with_call_depth(paren, |interpreter| {
+            interpreter.call_callee(callee, paren, arguments, named_arguments)
+        });
+    }
+
+    fn call_callee(
+        &mut self,
+        callee: Interpretable,
+        paren: &Token,
+        mut arguments: Vec<Interpretable>,
+        named_arguments: Vec<(Token, Interpretable)>,
+    ) -> Result<Interpretable, RuntimeEvent> {
+        match callee {
+            Interpretable::Callable(function) => {
+                return self.call(&function, paren, &mut arguments, named_arguments);
+            }
+
+            Interpretable::Class(class) => {
+                return self.call(&class, paren, &mut arguments, named_arguments);
+            }
+
+            Interpretable::Variant(variant) => {
+                return self.call(&variant, paren, &mut arguments, named_arguments);
             }
 
             _ => {
@@ -199,17 +766,127 @@ impl ExprVisitor<Result<Interpretable, RuntimeEvent>> for Interpreter {
         }
     }
 
+    fn evaluate_arguments(
+        &mut self,
+        arguments: &Vec<Expr>,
+        named_arguments: &Vec<(Token, Expr)>,
+    ) -> Result<Arguments, RuntimeEvent> {
+        let mut args_eval: Vec<Interpretable> = Vec::new();
+        for argument in arguments {
+            args_eval.push(self.evaluate(argument)?);
+        }
+
+        let mut named_eval: Vec<(Token, Interpretable)> = Vec::new();
+        for (name, argument) in named_arguments {
+            named_eval.push((name.clone(), self.evaluate(argument)?));
+        }
+
+        return Ok((args_eval, named_eval));
+    }
+
+    // Evaluates a returned value. A call to a user function in tail position isn't made here but handed back
+    // as a tail call, so the returning function is gone by the time its callee runs
+    fn evaluate_tail(&mut self, expression: &Expr) -> Result<Interpretable, RuntimeEvent> {
+        match expression {
+            Expr::Call {
+                callee,
+                paren,
+                arguments,
+                named_arguments,
+            } => {
+                let callee_eval = self.evaluate(callee)?;
+                let (args_eval, named_eval) = self.evaluate_arguments(arguments, named_arguments)?;
+
+                if let Interpretable::Callable(LoxFunction::UserFunction(function)) = callee_eval {
+                    return Err(RuntimeEvent::TailCall(Box::new(TailCall {
+                        function,
+                        paren: paren.clone(),
+                        arguments: args_eval,
+                        named_arguments: named_eval,
+                    })));
+                }
+
+                return self.call_value(callee_eval, paren, args_eval, named_eval);
+            }
+
+            Expr::Grouping { expression } => return self.evaluate_tail(expression),
+
+            Expr::Conditional {
+                condition,
+                then_branch,
+                else_branch,
+            } => {
+                if self.evaluate(condition)?.is_truthy() {
+                    return self.evaluate_tail(then_branch);
+                }
+
+                return self.evaluate_tail(else_branch);
+            }
+
+            _ => return self.evaluate(expression),
+        }
+    }
+
+    // A try statement has to see what its body's tail calls do, so they're made before leaving it
+    fn settle_tail_call(&mut self, result: Result<Interpretable, RuntimeEvent>) -> Result<Interpretable, RuntimeEvent> {
+        match result {
+            Err(RuntimeEvent::TailCall(tail_call)) => {
+                // Nested inside the try statement, so unlike a tail call it counts towards the call depth
+                let paren = tail_call.paren.clone();
+                let value = self.with_call_depth(&paren, |interpreter| tail_call.run(interpreter))?;
+
+                return Err(RuntimeEvent::new_return(value));
+            }
+            result => return result,
+        }
+    }
+
+    pub fn interpret(&mut self, statements: Vec<Stmt>) {
+        for statement in statements {
+            let result = self.execute(&statement);
+
+            if result.is_err() {
+                self.error_handler
+                    .runtime_error(result.err().expect("Invalid interpreter error state"));
+
+                // Later statements may rely on what failed, like the namespace of an import that didn't load
+                break;
+            }
+        }
+    }
+
+    pub fn had_runtime_error(&self) -> bool {
+        return self.error_handler.had_error;
+    }
+}
+
+impl ExprVisitor<Result<Interpretable, RuntimeEvent>> for Interpreter {
+    fn visit_binary_expr(&mut self, left: &Expr, operator: &Token, right: &Expr) -> Result<Interpretable, RuntimeEvent> {
+        let l_eval = self.evaluate(left)?;
+        let r_eval = self.evaluate(right)?;
+
+        return self.binary_operation(operator, l_eval, r_eval);
+    }
+
+    fn visit_call_expr(
+        &mut self,
+        callee: &Expr,
+        paren: &Token,
+        arguments: &Vec<Expr>,
+        named_arguments: &Vec<(Token, Expr)>,
+    ) -> Result<Interpretable, RuntimeEvent> {
+        let callee_eval = self.evaluate(callee)?;
+        let (args_eval, named_eval) = self.evaluate_arguments(arguments, named_arguments)?;
+
+        return self.call_value(callee_eval, paren, args_eval, named_eval);
+    }
+
     fn visit_grouping_expr(&mut self, expression: &Expr) -> Result<Interpretable, RuntimeEvent> {
         return self.evaluate(expression);
     }
 
     fn visit_literal_expr(&mut self, value: &Literal) -> Result<Interpretable, RuntimeEvent> {
-        match value {
-            Literal::Number(n) => return Ok(Interpretable::Number(*n)),
-            Literal::Boolean(b) => return Ok(Interpretable::Boolean(*b)),
-            Literal::String(s) => return Ok(Interpretable::String(s.clone())),
-            Literal::Nil => return Ok(Interpretable::Nil),
-        }
+        return Ok(literal_value(value));
     }
 
     fn visit_unary_expr(&mut self, operator: &Token, expr: &Expr) -> Result<Interpretable, RuntimeEvent> {
@@ -232,13 +909,47 @@ impl ExprVisitor<Result<Interpretable, RuntimeEvent>> for Interpreter {
         }
     }
 
-    fn visit_variable_expr(&mut self, name: &Token) -> Result<Interpretable, RuntimeEvent> {
-        return self.environment.borrow().get(name);
+    fn visit_variable_expr(&mut self, name: &Token, depth: &Cell<Option<usize>>) -> Result<Interpretable, RuntimeEvent> {
+        return self.look_up_variable(name, depth);
     }
 
-    fn visit_assignment_expr(&mut self, name: &Token, expr: &Expr) -> Result<Interpretable, RuntimeEvent> {
+    fn visit_assignment_expr(&mut self, name: &Token, expr: &Expr, depth: &Cell<Option<usize>>) -> Result<Interpretable, RuntimeEvent> {
         let value = self.evaluate(expr)?;
-        return self.environment.borrow_mut().assign(name, &value);
+        self.assign_variable(name, depth, &value)?;
+
+        return Ok(value);
+    }
+
+    fn visit_conditional_expr(&mut self, condition: &Expr, then_branch: &Expr, else_branch: &Expr) -> Result<Interpretable, RuntimeEvent> {
+        if self.evaluate(condition)?.is_truthy() {
+            return self.evaluate(then_branch);
+        }
+
+        return self.evaluate(else_branch);
+    }
+
+    fn visit_range_expr(
+        &mut self,
+        start: &Expr,
+        operator: &Token,
+        end: &Expr,
+        step: &Option<Box<Expr>>,
+    ) -> Result<Interpretable, RuntimeEvent> {
+        let start = self.evaluate(start)?;
+        let end = self.evaluate(end)?;
+
+        let step = match step {
+            Some(step) => self.evaluate(step)?,
+            None => Interpretable::Number(1.0),
+        };
+
+        match (start, end, step) {
+            (Interpretable::Number(start), Interpretable::Number(end), Interpretable::Number(step)) => {
+                let range = LoxRange::new(start, end, operator.kind == TokenKind::DotDotEqual);
+                return Ok(Interpretable::Range(range.with_step(operator, step)?));
+            }
+            _ => return Err(RuntimeEvent::interpreter_error(operator.clone(), "Range bounds and step must be numbers.")),
+        }
     }
 
     fn visit_logical_expr(&mut self, left: &Expr, operator: &Token, right: &Expr) -> Result<Interpretable, RuntimeEvent> {
@@ -265,6 +976,138 @@ impl ExprVisitor<Result<Interpretable, RuntimeEvent>> for Interpreter {
 
         return self.evaluate(right);
     }
+
+    fn visit_get_expr(&mut self, object: &Expr, name: &Token) -> Result<Interpretable, RuntimeEvent> {
+        match self.evaluate(object)? {
+            Interpretable::Instance(instance) => return LoxInstance::get(&instance, name),
+            Interpretable::Module(module) => return module.get(name),
+            Interpretable::Enum(lox_enum) => return lox_enum.get(name),
+            Interpretable::EnumValue(value) => return value.get(name),
+            Interpretable::Generator(generator) => return LoxGenerator::get(&generator, name),
+            _ => {
+                return Err(RuntimeEvent::interpreter_error(
+                    name.clone(),
+                    "Only instances have properties.",
+                ))
+            }
+        }
+    }
+
+    fn visit_set_expr(&mut self, object: &Expr, name: &Token, value: &Expr) -> Result<Interpretable, RuntimeEvent> {
+        let instance = match self.evaluate(object)? {
+            Interpretable::Instance(instance) => instance,
+            _ => return Err(RuntimeEvent::interpreter_error(name.clone(), "Only instances have fields.")),
+        };
+
+        let value = self.evaluate(value)?;
+        instance.borrow_mut().set(name, value.clone());
+
+        return Ok(value);
+    }
+
+    fn visit_this_expr(&mut self, keyword: &Token, depth: &Cell<Option<usize>>) -> Result<Interpretable, RuntimeEvent> {
+        return self.look_up_variable(keyword, depth);
+    }
+
+    fn visit_super_expr(&mut self, keyword: &Token, method: &Token, depth: &Cell<Option<usize>>) -> Result<Interpretable, RuntimeEvent> {
+        let distance = depth.get().expect("'super' is always resolved to a local scope.");
+
+        let superclass = match self.environment.borrow().get_at(distance, keyword)? {
+            Interpretable::Class(class) => class,
+            _ => unreachable!("'super' is always bound to a class."),
+        };
+
+        // The instance lives in the scope right inside the one binding 'super'
+        let this = Token::new(TokenKind::This, "this", None, keyword.line);
+        let instance = self.environment.borrow().get_at(distance - 1, &this)?;
+
+        match superclass.find_method(&method.lexeme) {
+            Some(LoxFunction::UserFunction(user_method)) => {
+                return Ok(Interpretable::Callable(user_method.bind(instance)));
+            }
+            _ => {
+                return Err(RuntimeEvent::interpreter_error(
+                    method.clone(),
+                    &format!("Undefined property '{}'.", method.lexeme),
+                ))
+            }
+        }
+    }
+
+    fn visit_list_expr(&mut self, elements: &Vec<Expr>) -> Result<Interpretable, RuntimeEvent> {
+        let mut values: Vec<Interpretable> = Vec::new();
+        for element in elements {
+            values.push(self.evaluate(element)?);
+        }
+
+        return Ok(Interpretable::List(Rc::new(RefCell::new(values))));
+    }
+
+    fn visit_interpolation_expr(&mut self, parts: &Vec<Expr>) -> Result<Interpretable, RuntimeEvent> {
+        let mut result = String::new();
+        for part in parts {
+            result.push_str(&self.evaluate(part)?.stringify());
+        }
+
+        return Ok(Interpretable::String(result));
+    }
+
+    fn visit_lambda_expr(&mut self, _keyword: &Token, parameters: &Vec<Parameter>, body: &Vec<Stmt>) -> Result<Interpretable, RuntimeEvent> {
+        let function = LoxFunction::new_user_function(None, parameters, body, self.environment.clone(), false);
+
+        return Ok(Interpretable::Callable(function));
+    }
+
+    fn visit_map_expr(&mut self, brace: &Token, entries: &Vec<(Expr, Expr)>) -> Result<Interpretable, RuntimeEvent> {
+        let mut map = LoxMap::new();
+        for (key, value) in entries {
+            let key = MapKey::from(brace, &self.evaluate(key)?)?;
+            let value = self.evaluate(value)?;
+
+            map.insert(key, value);
+        }
+
+        return Ok(Interpretable::Map(Rc::new(RefCell::new(map))));
+    }
+
+    fn visit_index_expr(&mut self, object: &Expr, bracket: &Token, index: &Expr) -> Result<Interpretable, RuntimeEvent> {
+        let object = self.evaluate(object)?;
+        let index = self.evaluate(index)?;
+
+        return self.index_get(&object, bracket, &index);
+    }
+
+    fn visit_index_set_expr(&mut self, object: &Expr, bracket: &Token, index: &Expr, value: &Expr) -> Result<Interpretable, RuntimeEvent> {
+        let object = self.evaluate(object)?;
+        let index = self.evaluate(index)?;
+        let value = self.evaluate(value)?;
+
+        return self.index_set(&object, bracket, &index, value);
+    }
+
+    fn visit_compound_assignment_expr(&mut self, target: &Expr, operator: &Token, value: &Expr) -> Result<Interpretable, RuntimeEvent> {
+        let operator = arithmetic_operator(operator);
+
+        return self.update_target(target, |interpreter, current| {
+            let value = interpreter.evaluate(value)?;
+            let result = interpreter.binary_operation(&operator, current, value)?;
+
+            return Ok((result.clone(), result));
+        });
+    }
+
+    fn visit_increment_expr(&mut self, target: &Expr, operator: &Token, is_prefix: bool) -> Result<Interpretable, RuntimeEvent> {
+        let operator = arithmetic_operator(operator);
+
+        return self.update_target(target, |interpreter, current| {
+            let result = interpreter.binary_operation(&operator, current.clone(), Interpretable::Number(1.0))?;
+
+            if is_prefix {
+                return Ok((result.clone(), result));
+            }
+            return Ok((result, current));
+        });
+    }
 }
 
 impl StmtVisitor<Result<Interpretable, RuntimeEvent>> for Interpreter {
@@ -300,21 +1143,59 @@ impl StmtVisitor<Result<Interpretable, RuntimeEvent>> for Interpreter {
             value = self.evaluate(initializer.as_ref().unwrap())?;
         }
 
-        self.environment.borrow_mut().define(name.lexeme.clone(), value);
+        self.define_variable(name, value, false)?;
         return Ok(Interpretable::Nil);
     }
 
-    fn visit_while_stmt(&mut self, condition: &Expr, body: &Stmt) -> Result<Interpretable, RuntimeEvent> {
+    fn visit_const_stmt(&mut self, name: &Token, initializer: &Expr) -> Result<Interpretable, RuntimeEvent> {
+        let value = self.evaluate(initializer)?;
+
+        self.define_variable(name, value, true)?;
+        return Ok(Interpretable::Nil);
+    }
+
+    fn visit_while_stmt(&mut self, condition: &Expr, body: &Stmt, increment: &Option<Box<Expr>>) -> Result<Interpretable, RuntimeEvent> {
         let mut predicate: Interpretable;
 
         loop {
             predicate = self.evaluate(condition)?;
 
-            if predicate.is_truthy() {
-                let _ = self.execute(body)?;
-            } else {
+            if !predicate.is_truthy() {
                 break;
             }
+
+            match self.execute(body) {
+                Err(RuntimeEvent::Break) => break,
+                Err(RuntimeEvent::Continue) | Ok(_) => {}
+                Err(other) => return Err(other),
+            }
+
+            if let Some(increment) = increment {
+                self.evaluate(increment)?;
+            }
+        }
+
+        return Ok(Interpretable::Nil);
+    }
+
+    fn visit_for_in_stmt(&mut self, keyword: &Token, name: &Token, iterable: &Expr, body: &Stmt) -> Result<Interpretable, RuntimeEvent> {
+        let iterable = self.evaluate(iterable)?;
+        let mut iterator = LoxIterator::new(self, keyword, iterable)?;
+
+        while let Some(value) = iterator.next(self, keyword)? {
+            // A fresh environment per iteration, so closures created in the body capture their own value
+            let mut environment = Environment::from(self.environment.clone());
+            environment.define(name.lexeme.clone(), value);
+
+            let previous = std::mem::replace(&mut self.environment, Rc::new(RefCell::new(environment)));
+            let result = self.execute(body);
+            self.environment = previous;
+
+            match result {
+                Err(RuntimeEvent::Break) => break,
+                Err(RuntimeEvent::Continue) | Ok(_) => {}
+                Err(other) => return Err(other),
+            }
         }
 
         return Ok(Interpretable::Nil);
@@ -328,12 +1209,10 @@ impl StmtVisitor<Result<Interpretable, RuntimeEvent>> for Interpreter {
         self.execute_block(declarations, block_environment)
     }
 
-    fn visit_function_stmt(&mut self, name: &Token, parameters: &Vec<Token>, body: &Vec<Stmt>) -> Result<Interpretable, RuntimeEvent> {
-        let function = LoxFunction::new_user_function(name, parameters, body, self.environment.clone());
+    fn visit_function_stmt(&mut self, name: &Token, parameters: &Vec<Parameter>, body: &Vec<Stmt>) -> Result<Interpretable, RuntimeEvent> {
+        let function = LoxFunction::new_user_function(Some(name), parameters, body, self.environment.clone(), false);
 
-        self.environment
-            .borrow_mut()
-            .define(name.to_string(), Interpretable::Callable(function));
+        self.define_variable(name, Interpretable::Callable(function), false)?;
 
         Ok(Interpretable::Nil)
     }
@@ -342,11 +1221,209 @@ impl StmtVisitor<Result<Interpretable, RuntimeEvent>> for Interpreter {
         let mut result = Interpretable::Nil;
 
         if *value != Expr::Nil {
-            result = self.evaluate(value)?;
+            result = self.evaluate_tail(value)?;
         }
 
         // This is terrible, honestly. It's not an error, but I don't feel like changing the return type for every function.
         // The return type should've been a variant (Interpretable, Error or Return) instead of a Result.
         return Err(RuntimeEvent::new_return(result));
     }
+
+    fn visit_class_stmt(&mut self, name: &Token, superclass: &Option<Expr>, methods: &Vec<Stmt>) -> Result<Interpretable, RuntimeEvent> {
+        let mut parent_class: Option<Rc<LoxClass>> = None;
+        if let Some(superclass_expr @ Expr::Variable { name: superclass_name, .. }) = superclass {
+            if superclass_name.lexeme == name.lexeme {
+                return Err(RuntimeEvent::interpreter_error(
+                    superclass_name.clone(),
+                    "A class can't inherit from itself.",
+                ));
+            }
+
+            match self.evaluate(superclass_expr)? {
+                Interpretable::Class(class) => parent_class = Some(class),
+                _ => {
+                    return Err(RuntimeEvent::interpreter_error(
+                        superclass_name.clone(),
+                        "Superclass must be a class.",
+                    ));
+                }
+            }
+        }
+
+        self.define_variable(name, Interpretable::Nil, false)?;
+
+        // Methods of a subclass close over an extra scope holding 'super'
+        let previous = self.environment.clone();
+        if let Some(parent_class) = &parent_class {
+            let mut super_environment = Environment::from(Rc::clone(&self.environment));
+            super_environment.define(String::from("super"), Interpretable::Class(parent_class.clone()));
+
+            self.environment = Rc::new(RefCell::new(super_environment));
+        }
+
+        let mut class_methods: HashMap<String, LoxFunction> = HashMap::new();
+        for method in methods {
+            if let Stmt::FunctionStmt {
+                name: method_name,
+                parameters,
+                body,
+            } = method
+            {
+                let is_initializer = method_name.lexeme == "init";
+                let function = LoxFunction::new_user_function(Some(method_name), parameters, body, self.environment.clone(), is_initializer);
+
+                class_methods.insert(method_name.lexeme.clone(), function);
+            }
+        }
+
+        self.environment = previous;
+
+        let class = LoxClass::new(name, parent_class, class_methods);
+        self.environment
+            .borrow_mut()
+            .assign(name, &Interpretable::Class(Rc::new(class)))?;
+
+        return Ok(Interpretable::Nil);
+    }
+
+    fn visit_enum_stmt(&mut self, name: &Token, variants: &Vec<(Token, Vec<Token>)>) -> Result<Interpretable, RuntimeEvent> {
+        let lox_enum = LoxEnum::new(name, variants);
+
+        self.define_variable(name, Interpretable::Enum(Rc::new(lox_enum)), false)?;
+        return Ok(Interpretable::Nil);
+    }
+
+    fn visit_break_stmt(&mut self, _keyword: &Token) -> Result<Interpretable, RuntimeEvent> {
+        // Same trick as return, unwinds up to the enclosing loop
+        return Err(RuntimeEvent::Break);
+    }
+
+    fn visit_continue_stmt(&mut self, _keyword: &Token) -> Result<Interpretable, RuntimeEvent> {
+        return Err(RuntimeEvent::Continue);
+    }
+
+    fn visit_match_stmt(&mut self, keyword: &Token, subject: &Expr, arms: &Vec<MatchArm>) -> Result<Interpretable, RuntimeEvent> {
+        let (index, environment) = self.select_match_arm(keyword, subject, arms, self.environment.clone())?;
+
+        return self.execute_in(std::slice::from_ref(&arms[index].body), environment);
+    }
+
+    fn visit_import_stmt(&mut self, keyword: &Token, path: &str, name: &Token) -> Result<Interpretable, RuntimeEvent> {
+        let module = self.load_module(keyword, path)?;
+
+        self.define_variable(name, Interpretable::Module(module), false)?;
+
+        return Ok(Interpretable::Nil);
+    }
+
+    fn visit_throw_stmt(&mut self, keyword: &Token, value: &Expr) -> Result<Interpretable, RuntimeEvent> {
+        let value = self.evaluate(value)?;
+
+        return Err(RuntimeEvent::new_throw(keyword.clone(), value));
+    }
+
+    // Generators step through their yields themselves, so this is only reached by a yield outside of one
+    fn visit_yield_stmt(&mut self, keyword: &Token, _value: &Expr) -> Result<Interpretable, RuntimeEvent> {
+        return Err(RuntimeEvent::interpreter_error(keyword.clone(), "Can't yield outside a generator."));
+    }
+
+    fn visit_try_stmt(
+        &mut self,
+        body: &Vec<Stmt>,
+        catch_clause: &Option<(Token, Vec<Stmt>)>,
+        finally_body: &Option<Vec<Stmt>>,
+    ) -> Result<Interpretable, RuntimeEvent> {
+        let mut result = self.execute_block(body, Environment::from(Rc::clone(&self.environment)));
+        result = self.settle_tail_call(result);
+
+        // Whatever isn't caught passes through to the finally body and then keeps unwinding
+        if let Some((name, handler)) = catch_clause {
+            let exception = match &result {
+                Err(event) => self.caught_value(event),
+                Ok(_) => None,
+            };
+
+            if let Some(exception) = exception {
+                let mut catch_environment = Environment::from(Rc::clone(&self.environment));
+                catch_environment.define(name.lexeme.clone(), exception);
+
+                result = self.execute_block(handler, catch_environment);
+                result = self.settle_tail_call(result);
+            }
+        }
+
+        if let Some(finally_body) = finally_body {
+            // Anything that unwinds out of the finally body replaces the pending result
+            self.execute_block(finally_body, Environment::from(Rc::clone(&self.environment)))?;
+        }
+
+        return result;
+    }
+}
+
+// Address of a local of the caller's frame, how far apart two of them are is the stack used in between
+fn stack_address() -> usize {
+    let marker = 0u8;
+    return std::hint::black_box(&marker) as *const u8 as usize;
+}
+
+// Literal patterns use the same equality as '==', ranges only match numbers
+fn matches_literal_pattern(pattern: &Pattern, value: &Interpretable) -> bool {
+    match (pattern, value) {
+        (Pattern::Wildcard, _) => return true,
+        (Pattern::Literal(literal), value) => return literal_value(literal) == *value,
+        (Pattern::Range { start, end, inclusive }, Interpretable::Number(n)) => {
+            return *start <= *n && if *inclusive { *n <= *end } else { *n < *end };
+        }
+        (Pattern::Range { .. }, _) => return false,
+        (Pattern::Variant { .. }, _) => unreachable!("Variant patterns are matched by the interpreter."),
+    }
+}
+
+fn literal_value(literal: &Literal) -> Interpretable {
+    match literal {
+        Literal::Number(n) => return Interpretable::Number(*n),
+        Literal::Boolean(b) => return Interpretable::Boolean(*b),
+        Literal::String(s) => return Interpretable::String(s.clone()),
+        Literal::Nil => return Interpretable::Nil,
+    }
+}
+
+// Maps the operator of a compound assignment or increment onto the arithmetic operator it applies. The lexeme is
+// kept so errors still point at the operator that was written
+fn arithmetic_operator(operator: &Token) -> Token {
+    let kind = match operator.kind {
+        TokenKind::PlusEqual | TokenKind::PlusPlus => TokenKind::Plus,
+        TokenKind::MinusEqual | TokenKind::MinusMinus => TokenKind::Minus,
+        TokenKind::StarEqual => TokenKind::Star,
+        TokenKind::SlashEqual => TokenKind::Slash,
+        _ => unreachable!("Not a compound assignment operator."),
+    };
+
+    return Token::new(kind, &operator.lexeme, None, operator.line);
+}
+
+// Every module gets its own copy of the native functions in its global environment, as constants so a script
+// can't overwrite them by accident. Declaring the same name still replaces one
+fn define_natives(environment: &mut Environment) {
+    let native_functions: [(&str, usize, FunctionBody); 12] = [
+        ("clock", 0, natives::native_clock_call),
+        ("len", 1, natives::native_len_call),
+        ("push", 2, natives::native_push_call),
+        ("pop", 1, natives::native_pop_call),
+        ("insert", 3, natives::native_insert_call),
+        ("remove", 2, natives::native_remove_call),
+        ("slice", 3, natives::native_slice_call),
+        ("sort", 1, natives::native_sort_call),
+        ("keys", 1, natives::native_keys_call),
+        ("values", 1, natives::native_values_call),
+        ("has", 2, natives::native_has_call),
+        ("delete", 2, natives::native_delete_call),
+    ];
+
+    for (name, arity, body) in native_functions {
+        let native_callable = LoxFunction::new_native_function(arity, body);
+
+        environment.define_native(String::from(name), Interpretable::Callable(native_callable));
+    }
 }
@@ -1,55 +1,269 @@
 use std::{
-    cell::RefCell, fmt::{Debug, Display}, rc::Rc, time::{SystemTime, UNIX_EPOCH}
+    cell::RefCell,
+    fmt::{Debug, Display},
+    rc::Rc,
 };
 
-use crate::{ast::Stmt, error::RuntimeEvent, token::Token};
+use crate::{
+    ast::{Parameter, Stmt},
+    error::RuntimeEvent,
+    token::{types::TokenKind, Token},
+};
 
+use super::lox_generator::{LoxGenerator, Resumable};
 use super::{Environment, Interpretable, Interpreter};
 
-type FunctionBody = fn(&mut Interpreter, &mut Vec<Interpretable>) -> Result<Interpretable, RuntimeEvent>;
+pub type FunctionBody = fn(&mut Interpreter, &Token, &mut Vec<Interpretable>) -> Result<Interpretable, RuntimeEvent>;
+
+/// Range of argument counts a callable accepts, without an upper bound when it has a rest parameter.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Arity {
+    pub min: usize,
+    pub max: Option<usize>,
+}
+
+impl Arity {
+    pub fn exact(count: usize) -> Self {
+        return Arity {
+            min: count,
+            max: Some(count),
+        };
+    }
+
+    pub fn accepts(&self, count: usize) -> bool {
+        return count >= self.min && self.max.is_none_or(|max| count <= max);
+    }
+}
+
+impl Display for Arity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.max {
+            Some(max) if max == self.min => return write!(f, "{}", max),
+            Some(max) => return write!(f, "{} to {}", self.min, max),
+            None => return write!(f, "at least {}", self.min),
+        }
+    }
+}
 
 pub trait LoxCallable: Debug + Clone {
-    fn arity(&self) -> usize;
-    fn call(&self, interpreter: &mut Interpreter, arguments: &mut Vec<Interpretable>) -> Result<Interpretable, RuntimeEvent>;
+    fn arity(&self) -> Arity;
+    fn call(&self, interpreter: &mut Interpreter, paren: &Token, arguments: &mut Vec<Interpretable>) -> Result<Interpretable, RuntimeEvent>;
+
+    // Only callables with named parameters can take named arguments, natives have nothing to match them against
+    fn call_named(
+        &self,
+        _interpreter: &mut Interpreter,
+        paren: &Token,
+        _arguments: Vec<Interpretable>,
+        _named_arguments: Vec<(Token, Interpretable)>,
+    ) -> Result<Interpretable, RuntimeEvent> {
+        return Err(RuntimeEvent::interpreter_error(
+            paren.clone(),
+            "Native functions don't accept named arguments.",
+        ));
+    }
+}
+
+pub fn check_arity(callee: &impl LoxCallable, paren: &Token, count: usize) -> Result<(), RuntimeEvent> {
+    if !callee.arity().accepts(count) {
+        return Err(RuntimeEvent::interpreter_error(
+            paren.clone(),
+            &format!("Expected {} arguments, but got {}.", callee.arity(), count),
+        ));
+    }
+
+    return Ok(());
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct NativeCallable {
     arity: usize,
     body: Box<FunctionBody>,
+    // Value a native method is bound to, passed to the body ahead of the arguments
+    receiver: Option<Box<Interpretable>>,
 }
 
 impl LoxCallable for NativeCallable {
-    fn arity(&self) -> usize {
-        return self.arity;
+    fn arity(&self) -> Arity {
+        return Arity::exact(self.arity);
     }
 
-    fn call(&self, interpreter: &mut Interpreter, arguments: &mut Vec<Interpretable>) -> Result<Interpretable, RuntimeEvent> {
-        return (self.body)(interpreter, arguments);
+    fn call(&self, interpreter: &mut Interpreter, paren: &Token, arguments: &mut Vec<Interpretable>) -> Result<Interpretable, RuntimeEvent> {
+        if let Some(receiver) = &self.receiver {
+            arguments.insert(0, (**receiver).clone());
+        }
+
+        return (self.body)(interpreter, paren, arguments);
     }
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct UserCallable {
-    name: Token,
-    parameters: Vec<Token>,
-    body: Vec<Stmt>,
-    closure: Rc<RefCell<Environment>>
+    name: Option<Token>,
+    parameters: Vec<Parameter>,
+    // Shared, binding a method to an instance copies the callable
+    body: Rc<Vec<Stmt>>,
+    // Split up body of a function containing `yield`, calling it then returns a generator instead
+    generator_body: Option<Rc<Resumable>>,
+    closure: Rc<RefCell<Environment>>,
+    is_initializer: bool,
+}
+
+impl UserCallable {
+    pub fn bind(&self, instance: Interpretable) -> LoxFunction {
+        let mut environment = Environment::from(self.closure.clone());
+        environment.define(String::from("this"), instance);
+
+        let mut bound = self.clone();
+        bound.closure = Rc::new(RefCell::new(environment));
+
+        return LoxFunction::UserFunction(bound);
+    }
+
+    fn this(&self) -> Result<Interpretable, RuntimeEvent> {
+        let line = self.name.as_ref().map_or(0, |name| name.line);
+        let keyword = Token::new(TokenKind::This, "this", None, line);
+        return self.closure.borrow().get_at(0, &keyword);
+    }
 }
 
 impl LoxCallable for UserCallable {
-    fn arity(&self) -> usize {
-        return self.parameters.len();
+    fn arity(&self) -> Arity {
+        let required = self
+            .parameters
+            .iter()
+            .filter(|param| param.default.is_none() && !param.is_rest)
+            .count();
+
+        if self.parameters.last().is_some_and(|param| param.is_rest) {
+            return Arity {
+                min: required,
+                max: None,
+            };
+        }
+
+        return Arity {
+            min: required,
+            max: Some(self.parameters.len()),
+        };
     }
 
-    fn call(&self, interpreter: &mut Interpreter, arguments: &mut Vec<Interpretable>, ) -> Result<Interpretable, RuntimeEvent> {
-        let mut environment = Environment::from(self.closure.clone());
+    fn call(&self, interpreter: &mut Interpreter, paren: &Token, arguments: &mut Vec<Interpretable>) -> Result<Interpretable, RuntimeEvent> {
+        return self.call_named(interpreter, paren, std::mem::take(arguments), Vec::new());
+    }
+
+    fn call_named(
+        &self,
+        interpreter: &mut Interpreter,
+        paren: &Token,
+        arguments: Vec<Interpretable>,
+        named_arguments: Vec<(Token, Interpretable)>,
+    ) -> Result<Interpretable, RuntimeEvent> {
+        // A call in tail position hands its callee back instead of making the call, so a chain of them
+        // runs in a loop rather than growing the stack
+        match self.invoke(interpreter, paren, arguments, named_arguments) {
+            Err(RuntimeEvent::TailCall(tail_call)) => return tail_call.run(interpreter),
+            result => return result,
+        }
+    }
+}
+
+impl UserCallable {
+    // Calls the function once, a `return f(...)` in its body comes back as a pending tail call
+    fn invoke(
+        &self,
+        interpreter: &mut Interpreter,
+        paren: &Token,
+        arguments: Vec<Interpretable>,
+        named_arguments: Vec<(Token, Interpretable)>,
+    ) -> Result<Interpretable, RuntimeEvent> {
+        let positional_count = arguments.len();
+        let has_rest = self.parameters.last().is_some_and(|param| param.is_rest);
+
+        // One slot per parameter except the rest one, positional arguments first and then the named ones
+        let mut slots: Vec<Option<Interpretable>> = vec![None; self.parameters.len() - has_rest as usize];
+        let mut rest: Vec<Interpretable> = Vec::new();
 
-        for (param, arg) in self.parameters.iter().zip(arguments) {
-            environment.define(param.lexeme.clone(), arg.clone());
+        for (index, argument) in arguments.into_iter().enumerate() {
+            match slots.get_mut(index) {
+                Some(slot) => *slot = Some(argument),
+                None => rest.push(argument),
+            }
         }
 
-        match interpreter.execute_block(&self.body, environment) {
+        if !rest.is_empty() && !has_rest {
+            return Err(RuntimeEvent::interpreter_error(
+                paren.clone(),
+                &format!("Expected {} arguments, but got {}.", self.arity(), positional_count),
+            ));
+        }
+
+        for (name, argument) in named_arguments {
+            let Some(index) = self.parameters.iter().position(|param| param.name.lexeme == name.lexeme) else {
+                return Err(RuntimeEvent::interpreter_error(
+                    name.clone(),
+                    &format!("Unknown argument '{}' for {}.", name.lexeme, LoxFunction::UserFunction(self.clone())),
+                ));
+            };
+
+            if self.parameters[index].is_rest {
+                return Err(RuntimeEvent::interpreter_error(
+                    name.clone(),
+                    &format!("Rest parameter '{}' can't be passed by name.", name.lexeme),
+                ));
+            }
+
+            if slots[index].is_some() {
+                return Err(RuntimeEvent::interpreter_error(
+                    name.clone(),
+                    &format!("Argument '{}' was already passed by position.", name.lexeme),
+                ));
+            }
+
+            slots[index] = Some(argument);
+        }
+
+        let environment = Rc::new(RefCell::new(Environment::from(self.closure.clone())));
+
+        let mut slots = slots.into_iter();
+        for param in &self.parameters {
+            if param.is_rest {
+                let rest = Interpretable::List(Rc::new(RefCell::new(std::mem::take(&mut rest))));
+                environment.borrow_mut().define(param.name.lexeme.clone(), rest);
+                continue;
+            }
+
+            let value = match (slots.next().flatten(), &param.default) {
+                (Some(argument), _) => argument,
+                // Evaluated inside the new environment, so a default can use the parameters before it
+                (None, Some(default)) => interpreter.evaluate_in(default, environment.clone())?,
+                (None, None) => {
+                    return Err(RuntimeEvent::interpreter_error(
+                        paren.clone(),
+                        &format!("Missing argument for parameter '{}'.", param.name.lexeme),
+                    ))
+                }
+            };
+
+            environment.borrow_mut().define(param.name.lexeme.clone(), value);
+        }
+
+        if let Some(generator_body) = &self.generator_body {
+            let generator = LoxGenerator::new(self.name.clone(), generator_body.clone(), environment);
+            return Ok(Interpretable::Generator(Rc::new(generator)));
+        }
+
+        let result = interpreter.execute_in(&self.body, environment);
+
+        // Initializers always hand back the instance, even on an early `return;`
+        if self.is_initializer {
+            return match result {
+                Err(RuntimeEvent::Return(_)) | Ok(_) => self.this(),
+                Err(other) => Err(other),
+            };
+        }
+
+        match result {
             Err(RuntimeEvent::Return(value)) => Ok(value),
             Err(other) => Err(other),
             Ok(_) => Ok(Interpretable::Nil),
@@ -57,6 +271,38 @@ impl LoxCallable for UserCallable {
     }
 }
 
+/// Call made by `return f(...)` to a user function, left for the caller's `call_named` to perform once the
+/// returning function's frame is gone.
+#[derive(Debug)]
+pub struct TailCall {
+    pub function: UserCallable,
+    pub paren: Token,
+    pub arguments: Vec<Interpretable>,
+    pub named_arguments: Vec<(Token, Interpretable)>,
+}
+
+impl TailCall {
+    /// Makes the call, which may itself end in another tail call for the caller to loop on.
+    pub fn invoke(self, interpreter: &mut Interpreter) -> Result<Interpretable, RuntimeEvent> {
+        if self.named_arguments.is_empty() {
+            check_arity(&self.function, &self.paren, self.arguments.len())?;
+        }
+
+        return self.function.invoke(interpreter, &self.paren, self.arguments, self.named_arguments);
+    }
+
+    /// Makes the call and every tail call following it, for places that need the final value.
+    pub fn run(self, interpreter: &mut Interpreter) -> Result<Interpretable, RuntimeEvent> {
+        let mut result = self.invoke(interpreter);
+
+        while let Err(RuntimeEvent::TailCall(tail_call)) = result {
+            result = tail_call.invoke(interpreter);
+        }
+
+        return result;
+    }
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub enum LoxFunction {
     NativeFunction(NativeCallable),
@@ -68,16 +314,34 @@ impl LoxFunction {
         let native_call = NativeCallable {
             arity,
             body: Box::new(body),
+            receiver: None,
         };
         return LoxFunction::NativeFunction(native_call);
     }
 
-    pub fn new_user_function(name: &Token, parameters: &Vec<Token>, body: &Vec<Stmt>, closure: Rc<RefCell<Environment>>) -> Self {
+    pub fn new_native_method(arity: usize, body: FunctionBody, receiver: Interpretable) -> Self {
+        let native_call = NativeCallable {
+            arity,
+            body: Box::new(body),
+            receiver: Some(Box::new(receiver)),
+        };
+        return LoxFunction::NativeFunction(native_call);
+    }
+
+    pub fn new_user_function(
+        name: Option<&Token>,
+        parameters: &Vec<Parameter>,
+        body: &Vec<Stmt>,
+        closure: Rc<RefCell<Environment>>,
+        is_initializer: bool,
+    ) -> Self {
         let user_call = UserCallable {
-            name: name.clone(),
+            name: name.cloned(),
             parameters: parameters.clone(),
-            body: body.clone(),
-            closure:closure,
+            body: Rc::new(body.clone()),
+            generator_body: Resumable::from_body(body),
+            closure: closure,
+            is_initializer,
         };
 
         return LoxFunction::UserFunction(user_call);
@@ -85,17 +349,30 @@ impl LoxFunction {
 }
 
 impl LoxCallable for LoxFunction {
-    fn arity(&self) -> usize {
+    fn arity(&self) -> Arity {
         match self {
             LoxFunction::NativeFunction(n) => n.arity(),
             LoxFunction::UserFunction(u) => u.arity(),
         }
     }
 
-    fn call(&self, interpreter: &mut Interpreter, arguments: &mut Vec<Interpretable>) -> Result<Interpretable, RuntimeEvent> {
+    fn call(&self, interpreter: &mut Interpreter, paren: &Token, arguments: &mut Vec<Interpretable>) -> Result<Interpretable, RuntimeEvent> {
+        match self {
+            LoxFunction::NativeFunction(n) => n.call(interpreter, paren, arguments),
+            LoxFunction::UserFunction(u) => u.call(interpreter, paren, arguments),
+        }
+    }
+
+    fn call_named(
+        &self,
+        interpreter: &mut Interpreter,
+        paren: &Token,
+        arguments: Vec<Interpretable>,
+        named_arguments: Vec<(Token, Interpretable)>,
+    ) -> Result<Interpretable, RuntimeEvent> {
         match self {
-            LoxFunction::NativeFunction(n) => n.call(interpreter, arguments),
-            LoxFunction::UserFunction(u) => u.call(interpreter, arguments),
+            LoxFunction::NativeFunction(n) => n.call_named(interpreter, paren, arguments, named_arguments),
+            LoxFunction::UserFunction(u) => u.call_named(interpreter, paren, arguments, named_arguments),
         }
     }
 }
@@ -104,13 +381,10 @@ impl Display for LoxFunction {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             LoxFunction::NativeFunction(_) => return write!(f, "<fn native>"),
-            LoxFunction::UserFunction(u) => return write!(f, "<fn {}>", u.name),
+            LoxFunction::UserFunction(u) => match &u.name {
+                Some(name) => return write!(f, "<fn {}>", name),
+                None => return write!(f, "<fn anonymous>"),
+            },
         }
     }
 }
-
-// Native Call implementations
-pub fn native_clock_call(_interpreter: &mut Interpreter, _args: &mut Vec<Interpretable>) -> Result<Interpretable, RuntimeEvent> {
-    let now = SystemTime::now().duration_since(UNIX_EPOCH).expect("Time went backwards!");
-    return Ok(Interpretable::Number((now.as_millis() / 1000) as f64));
-}
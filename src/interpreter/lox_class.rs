@@ -0,0 +1,147 @@
+use std::{cell::RefCell, collections::HashMap, fmt::Display, rc::Rc};
+
+use crate::{error::RuntimeEvent, token::Token};
+
+use super::{
+    lox_callable::{Arity, LoxCallable, LoxFunction},
+    Interpretable, Interpreter,
+};
+
+#[derive(Debug)]
+pub struct LoxClass {
+    pub name: Token,
+    superclass: Option<Rc<LoxClass>>,
+    methods: HashMap<String, LoxFunction>,
+}
+
+impl LoxClass {
+    pub fn new(name: &Token, superclass: Option<Rc<LoxClass>>, methods: HashMap<String, LoxFunction>) -> Self {
+        return LoxClass {
+            name: name.clone(),
+            superclass,
+            methods,
+        };
+    }
+
+    pub fn find_method(&self, name: &str) -> Option<&LoxFunction> {
+        if let Some(method) = self.methods.get(name) {
+            return Some(method);
+        }
+
+        if let Some(superclass) = &self.superclass {
+            return superclass.find_method(name);
+        }
+
+        return None;
+    }
+}
+
+// Classes are compared by identity, two declarations with the same body are still different classes
+impl PartialEq for LoxClass {
+    fn eq(&self, other: &Self) -> bool {
+        return std::ptr::eq(self, other);
+    }
+}
+
+impl LoxCallable for Rc<LoxClass> {
+    fn arity(&self) -> Arity {
+        match self.find_method("init") {
+            Some(initializer) => initializer.arity(),
+            None => Arity::exact(0),
+        }
+    }
+
+    fn call(&self, interpreter: &mut Interpreter, paren: &Token, arguments: &mut Vec<Interpretable>) -> Result<Interpretable, RuntimeEvent> {
+        let instance = Interpretable::Instance(Rc::new(RefCell::new(LoxInstance::new(self.clone()))));
+
+        if let Some(LoxFunction::UserFunction(initializer)) = self.find_method("init") {
+            initializer.bind(instance.clone()).call(interpreter, paren, arguments)?;
+        }
+
+        return Ok(instance);
+    }
+
+    fn call_named(
+        &self,
+        interpreter: &mut Interpreter,
+        paren: &Token,
+        arguments: Vec<Interpretable>,
+        named_arguments: Vec<(Token, Interpretable)>,
+    ) -> Result<Interpretable, RuntimeEvent> {
+        let instance = Interpretable::Instance(Rc::new(RefCell::new(LoxInstance::new(self.clone()))));
+
+        match self.find_method("init") {
+            Some(LoxFunction::UserFunction(initializer)) => {
+                initializer
+                    .bind(instance.clone())
+                    .call_named(interpreter, paren, arguments, named_arguments)?;
+            }
+            _ => {
+                let (name, _) = &named_arguments[0];
+                return Err(RuntimeEvent::interpreter_error(
+                    name.clone(),
+                    &format!("Unknown argument '{}' for {}.", name.lexeme, self.name),
+                ));
+            }
+        }
+
+        return Ok(instance);
+    }
+}
+
+impl Display for LoxClass {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        return write!(f, "{}", self.name);
+    }
+}
+
+#[derive(Debug)]
+pub struct LoxInstance {
+    class: Rc<LoxClass>,
+    fields: HashMap<String, Interpretable>,
+}
+
+impl LoxInstance {
+    pub fn new(class: Rc<LoxClass>) -> Self {
+        return LoxInstance {
+            class,
+            fields: HashMap::new(),
+        };
+    }
+
+    pub fn get(instance: &Rc<RefCell<LoxInstance>>, name: &Token) -> Result<Interpretable, RuntimeEvent> {
+        if let Some(value) = instance.borrow().fields.get(&name.lexeme) {
+            return Ok(value.clone());
+        }
+
+        if let Some(LoxFunction::UserFunction(method)) = instance.borrow().class.find_method(&name.lexeme) {
+            return Ok(Interpretable::Callable(method.bind(Interpretable::Instance(instance.clone()))));
+        }
+
+        return Err(RuntimeEvent::interpreter_error(
+            name.clone(),
+            &format!("Undefined property '{}'.", name.lexeme),
+        ));
+    }
+
+    pub fn has(&self, name: &str) -> bool {
+        return self.fields.contains_key(name) || self.class.find_method(name).is_some();
+    }
+
+    pub fn set(&mut self, name: &Token, value: Interpretable) {
+        self.fields.insert(name.lexeme.clone(), value);
+    }
+}
+
+// Same as classes, instances are only equal to themselves
+impl PartialEq for LoxInstance {
+    fn eq(&self, other: &Self) -> bool {
+        return std::ptr::eq(self, other);
+    }
+}
+
+impl Display for LoxInstance {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        return write!(f, "{} instance", self.class.name);
+    }
+}
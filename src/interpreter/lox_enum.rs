@@ -0,0 +1,207 @@
+use std::{fmt::Display, rc::Rc};
+
+use crate::{error::RuntimeEvent, token::Token};
+
+use super::{
+    lox_callable::{Arity, LoxCallable},
+    Interpretable, Interpreter,
+};
+
+/// Value bound by an `enum` declaration, a namespace holding its variants.
+#[derive(Debug)]
+pub struct LoxEnum {
+    pub name: Token,
+    variants: Vec<Rc<LoxVariant>>,
+}
+
+impl LoxEnum {
+    pub fn new(name: &Token, variants: &Vec<(Token, Vec<Token>)>) -> Self {
+        let variants = variants
+            .iter()
+            .map(|(variant, fields)| {
+                Rc::new(LoxVariant {
+                    enum_name: name.lexeme.clone(),
+                    name: variant.lexeme.clone(),
+                    fields: fields.iter().map(|field| field.lexeme.clone()).collect(),
+                })
+            })
+            .collect();
+
+        return LoxEnum {
+            name: name.clone(),
+            variants,
+        };
+    }
+
+    pub fn find_variant(&self, name: &Token) -> Result<Rc<LoxVariant>, RuntimeEvent> {
+        match self.variants.iter().find(|variant| variant.name == name.lexeme) {
+            Some(variant) => return Ok(variant.clone()),
+            None => {
+                return Err(RuntimeEvent::interpreter_error(
+                    name.clone(),
+                    &format!("Enum '{}' has no variant '{}'.", self.name.lexeme, name.lexeme),
+                ))
+            }
+        }
+    }
+
+    // Variants without fields are values on their own, the others are constructors
+    pub fn get(&self, name: &Token) -> Result<Interpretable, RuntimeEvent> {
+        let variant = self.find_variant(name)?;
+
+        if variant.fields.is_empty() {
+            return Ok(Interpretable::EnumValue(Rc::new(LoxEnumValue::new(variant, Vec::new()))));
+        }
+
+        return Ok(Interpretable::Variant(variant));
+    }
+}
+
+// Same as classes, two declarations of the same enum are still different enums
+impl PartialEq for LoxEnum {
+    fn eq(&self, other: &Self) -> bool {
+        return std::ptr::eq(self, other);
+    }
+}
+
+impl Display for LoxEnum {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        return write!(f, "<enum {}>", self.name.lexeme);
+    }
+}
+
+/// One variant of an enum. Calling it builds a value tagged with the variant.
+#[derive(Debug)]
+pub struct LoxVariant {
+    enum_name: String,
+    name: String,
+    pub fields: Vec<String>,
+}
+
+impl LoxVariant {
+    fn qualified_name(&self) -> String {
+        return format!("{}.{}", self.enum_name, self.name);
+    }
+}
+
+// Each variant belongs to a single declaration, so comparing them by identity also tells enums apart
+impl PartialEq for LoxVariant {
+    fn eq(&self, other: &Self) -> bool {
+        return std::ptr::eq(self, other);
+    }
+}
+
+impl LoxCallable for Rc<LoxVariant> {
+    fn arity(&self) -> Arity {
+        return Arity::exact(self.fields.len());
+    }
+
+    fn call(&self, _interpreter: &mut Interpreter, _paren: &Token, arguments: &mut Vec<Interpretable>) -> Result<Interpretable, RuntimeEvent> {
+        let value = LoxEnumValue::new(self.clone(), std::mem::take(arguments));
+        return Ok(Interpretable::EnumValue(Rc::new(value)));
+    }
+
+    fn call_named(
+        &self,
+        _interpreter: &mut Interpreter,
+        paren: &Token,
+        arguments: Vec<Interpretable>,
+        named_arguments: Vec<(Token, Interpretable)>,
+    ) -> Result<Interpretable, RuntimeEvent> {
+        if arguments.len() > self.fields.len() {
+            return Err(RuntimeEvent::interpreter_error(
+                paren.clone(),
+                &format!("Expected {} arguments, but got {}.", self.arity(), arguments.len()),
+            ));
+        }
+
+        let mut slots: Vec<Option<Interpretable>> = vec![None; self.fields.len()];
+        for (slot, argument) in slots.iter_mut().zip(arguments) {
+            *slot = Some(argument);
+        }
+
+        for (name, argument) in named_arguments {
+            let Some(index) = self.fields.iter().position(|field| *field == name.lexeme) else {
+                return Err(RuntimeEvent::interpreter_error(
+                    name.clone(),
+                    &format!("Unknown argument '{}' for {}.", name.lexeme, self.qualified_name()),
+                ));
+            };
+
+            if slots[index].is_some() {
+                return Err(RuntimeEvent::interpreter_error(
+                    name.clone(),
+                    &format!("Argument '{}' was already passed by position.", name.lexeme),
+                ));
+            }
+
+            slots[index] = Some(argument);
+        }
+
+        let mut values: Vec<Interpretable> = Vec::new();
+        for (field, slot) in self.fields.iter().zip(slots) {
+            match slot {
+                Some(value) => values.push(value),
+                None => {
+                    return Err(RuntimeEvent::interpreter_error(
+                        paren.clone(),
+                        &format!("Missing argument for parameter '{}'.", field),
+                    ))
+                }
+            }
+        }
+
+        return Ok(Interpretable::EnumValue(Rc::new(LoxEnumValue::new(self.clone(), values))));
+    }
+}
+
+impl Display for LoxVariant {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        return write!(f, "<variant {}>", self.qualified_name());
+    }
+}
+
+/// Immutable value built from an enum variant, holding one value per field of the variant.
+#[derive(Debug)]
+pub struct LoxEnumValue {
+    pub variant: Rc<LoxVariant>,
+    pub values: Vec<Interpretable>,
+}
+
+impl LoxEnumValue {
+    pub fn new(variant: Rc<LoxVariant>, values: Vec<Interpretable>) -> Self {
+        return LoxEnumValue { variant, values };
+    }
+
+    pub fn get(&self, name: &Token) -> Result<Interpretable, RuntimeEvent> {
+        match self.variant.fields.iter().position(|field| *field == name.lexeme) {
+            Some(index) => return Ok(self.values[index].clone()),
+            None => {
+                return Err(RuntimeEvent::interpreter_error(
+                    name.clone(),
+                    &format!("Undefined property '{}'.", name.lexeme),
+                ))
+            }
+        }
+    }
+}
+
+// Unlike instances, enum values are compared by content: same variant and equal fields
+impl PartialEq for LoxEnumValue {
+    fn eq(&self, other: &Self) -> bool {
+        return Rc::ptr_eq(&self.variant, &other.variant) && self.values == other.values;
+    }
+}
+
+impl Display for LoxEnumValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.variant.qualified_name())?;
+
+        if self.variant.fields.is_empty() {
+            return Ok(());
+        }
+
+        let values: Vec<String> = self.values.iter().map(|value| format!("{}", value)).collect();
+        return write!(f, "({})", values.join(", "));
+    }
+}
@@ -0,0 +1,572 @@
+use std::{
+    cell::{RefCell, RefMut},
+    fmt::{Debug, Display},
+    rc::Rc,
+};
+
+use crate::{
+    ast::{Expr, MatchArm, Stmt},
+    error::RuntimeEvent,
+    token::Token,
+};
+
+use super::{lox_callable::LoxFunction, lox_iterator::LoxIterator, Environment, Interpretable, Interpreter};
+
+/// Body of a generator function, split so that every statement which can reach a `yield` is run step by step
+/// by the generator instead of recursively on the Rust stack. Statements without a yield run in one go.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Resumable {
+    Simple(Stmt),
+    Yield(Expr),
+    Block(Vec<Rc<Resumable>>),
+    If {
+        condition: Expr,
+        then_branch: Rc<Resumable>,
+        else_branch: Option<Rc<Resumable>>,
+    },
+    While {
+        condition: Expr,
+        body: Rc<Resumable>,
+        increment: Option<Expr>,
+    },
+    ForIn {
+        keyword: Token,
+        name: Token,
+        iterable: Expr,
+        body: Rc<Resumable>,
+    },
+    Match {
+        keyword: Token,
+        subject: Expr,
+        arms: Vec<MatchArm>,
+        bodies: Vec<Rc<Resumable>>,
+    },
+    // Each part is a block, the body of one is always stepped through so its errors reach the catch clause
+    Try {
+        body: Rc<Resumable>,
+        catch_clause: Option<(Token, Rc<Resumable>)>,
+        finally_body: Option<Rc<Resumable>>,
+    },
+}
+
+impl Resumable {
+    /// Splits a function body, or returns `None` when it has no yield and so isn't a generator.
+    pub fn from_body(body: &[Stmt]) -> Option<Rc<Resumable>> {
+        if !body.iter().any(Stmt::contains_yield) {
+            return None;
+        }
+
+        return Some(Resumable::from_block(body));
+    }
+
+    fn from_block(statements: &[Stmt]) -> Rc<Resumable> {
+        return Rc::new(Resumable::Block(statements.iter().map(Resumable::from_stmt).collect()));
+    }
+
+    fn from_stmt(statement: &Stmt) -> Rc<Resumable> {
+        if !statement.contains_yield() {
+            return Rc::new(Resumable::Simple(statement.clone()));
+        }
+
+        let resumable = match statement {
+            Stmt::YieldStmt { keyword: _, value } => Resumable::Yield(*value.clone()),
+            Stmt::BlockStmt { declarations } => Resumable::Block(declarations.iter().map(Resumable::from_stmt).collect()),
+            Stmt::IfStmt {
+                condition,
+                then_branch,
+                else_branch,
+            } => Resumable::If {
+                condition: *condition.clone(),
+                then_branch: Resumable::from_stmt(then_branch),
+                else_branch: else_branch.as_ref().as_ref().map(Resumable::from_stmt),
+            },
+            Stmt::WhileStmt {
+                condition,
+                body,
+                increment,
+            } => Resumable::While {
+                condition: *condition.clone(),
+                body: Resumable::from_stmt(body),
+                increment: increment.as_ref().map(|increment| *increment.clone()),
+            },
+            Stmt::ForInStmt {
+                keyword,
+                name,
+                iterable,
+                body,
+            } => Resumable::ForIn {
+                keyword: keyword.clone(),
+                name: name.clone(),
+                iterable: *iterable.clone(),
+                body: Resumable::from_stmt(body),
+            },
+            Stmt::MatchStmt { keyword, subject, arms } => Resumable::Match {
+                keyword: keyword.clone(),
+                subject: *subject.clone(),
+                arms: arms.clone(),
+                bodies: arms.iter().map(|arm| Resumable::from_stmt(&arm.body)).collect(),
+            },
+            Stmt::TryStmt {
+                body,
+                catch_clause,
+                finally_body,
+            } => Resumable::Try {
+                body: Resumable::from_block(body),
+                catch_clause: catch_clause
+                    .as_ref()
+                    .map(|(name, handler)| (name.clone(), Resumable::from_block(handler))),
+                finally_body: finally_body.as_deref().map(Resumable::from_block),
+            },
+            _ => unreachable!("Statement can't contain a yield."),
+        };
+
+        return Rc::new(resumable);
+    }
+}
+
+/// Progress through one statement of the body that is still running while the generator is suspended.
+enum Frame {
+    Block {
+        block: Rc<Resumable>,
+        index: usize,
+        environment: Rc<RefCell<Environment>>,
+    },
+    // The increment only runs from the second check of the condition on
+    While {
+        node: Rc<Resumable>,
+        environment: Rc<RefCell<Environment>>,
+        started: bool,
+    },
+    ForIn {
+        node: Rc<Resumable>,
+        iterator: LoxIterator,
+        environment: Rc<RefCell<Environment>>,
+    },
+    // Sits below the frame of the part currently running, to catch what unwinds out of it
+    Try {
+        node: Rc<Resumable>,
+        environment: Rc<RefCell<Environment>>,
+        stage: TryStage,
+    },
+}
+
+enum TryStage {
+    Body,
+    Catch,
+    // Holds the event that keeps unwinding once the finally body is done
+    Finally(Option<RuntimeEvent>),
+}
+
+fn block_frame(block: &Rc<Resumable>, environment: Environment) -> Frame {
+    return Frame::Block {
+        block: block.clone(),
+        index: 0,
+        environment: Rc::new(RefCell::new(environment)),
+    };
+}
+
+/// Value returned by calling a generator function. Its body only runs when values are asked for, up to the
+/// next `yield`, and the frames left on its stack remember where to resume.
+pub struct LoxGenerator {
+    name: Option<Token>,
+    // Borrowed while the body runs, so a body asking its own generator for a value can be caught
+    state: RefCell<GeneratorState>,
+}
+
+struct GeneratorState {
+    frames: Vec<Frame>,
+    // Value produced ahead of time by `hasNext()`, `Some(None)` once the body finished
+    peeked: Option<Option<Interpretable>>,
+}
+
+impl LoxGenerator {
+    pub fn new(name: Option<Token>, body: Rc<Resumable>, environment: Rc<RefCell<Environment>>) -> Self {
+        // The body runs straight in the environment of the parameters, same as a regular call
+        let frame = Frame::Block {
+            block: body,
+            index: 0,
+            environment,
+        };
+
+        let state = GeneratorState {
+            frames: vec![frame],
+            peeked: None,
+        };
+
+        return LoxGenerator {
+            name,
+            state: RefCell::new(state),
+        };
+    }
+
+    pub fn get(generator: &Rc<LoxGenerator>, name: &Token) -> Result<Interpretable, RuntimeEvent> {
+        let receiver = Interpretable::Generator(generator.clone());
+
+        match name.lexeme.as_str() {
+            "next" => return Ok(Interpretable::Callable(LoxFunction::new_native_method(0, native_next_call, receiver))),
+            "hasNext" => {
+                return Ok(Interpretable::Callable(LoxFunction::new_native_method(
+                    0,
+                    native_has_next_call,
+                    receiver,
+                )))
+            }
+            _ => {
+                return Err(RuntimeEvent::interpreter_error(
+                    name.clone(),
+                    &format!("Undefined property '{}'.", name.lexeme),
+                ))
+            }
+        }
+    }
+
+    /// Resumes the body up to its next `yield`, or returns `None` once it finished.
+    pub fn next(&self, interpreter: &mut Interpreter, token: &Token) -> Result<Option<Interpretable>, RuntimeEvent> {
+        let mut state = self.running(token)?;
+
+        if let Some(peeked) = state.peeked.take() {
+            return Ok(peeked);
+        }
+
+        // The body runs on the native stack like a call would, so resuming it counts towards the call depth
+        return interpreter.with_call_depth(token, |interpreter| state.resume(interpreter));
+    }
+
+    pub fn has_next(&self, interpreter: &mut Interpreter, token: &Token) -> Result<bool, RuntimeEvent> {
+        let mut state = self.running(token)?;
+
+        if state.peeked.is_none() {
+            let value = interpreter.with_call_depth(token, |interpreter| state.resume(interpreter))?;
+            state.peeked = Some(value);
+        }
+
+        return Ok(matches!(state.peeked, Some(Some(_))));
+    }
+
+    fn running(&self, token: &Token) -> Result<RefMut<'_, GeneratorState>, RuntimeEvent> {
+        return self
+            .state
+            .try_borrow_mut()
+            .map_err(|_| RuntimeEvent::interpreter_error(token.clone(), "Generator is already running."));
+    }
+}
+
+impl GeneratorState {
+    // A finished or failed generator drops its frames, so it stays finished
+    fn resume(&mut self, interpreter: &mut Interpreter) -> Result<Option<Interpretable>, RuntimeEvent> {
+        let result = self.run_until_yield(interpreter);
+
+        if !matches!(result, Ok(Some(_))) {
+            self.frames.clear();
+        }
+
+        match result {
+            Err(RuntimeEvent::Return(_)) => return Ok(None),
+            other => return other,
+        }
+    }
+
+    fn run_until_yield(&mut self, interpreter: &mut Interpreter) -> Result<Option<Interpretable>, RuntimeEvent> {
+        while !self.frames.is_empty() {
+            match self.step(interpreter) {
+                Ok(Some(value)) => return Ok(Some(value)),
+                Ok(None) => {}
+                Err(event) => self.unwind(interpreter, event)?,
+            }
+        }
+
+        return Ok(None);
+    }
+
+    // Advances the frame on top of the stack by one statement
+    fn step(&mut self, interpreter: &mut Interpreter) -> Result<Option<Interpretable>, RuntimeEvent> {
+        if let Some(frame) = self.frames.last_mut() {
+            let (node, environment) = match frame {
+                Frame::Block {
+                    block,
+                    index,
+                    environment,
+                } => {
+                    let Resumable::Block(statements) = &**block else {
+                        unreachable!("Block frames always hold a block.");
+                    };
+
+                    let Some(node) = statements.get(*index).cloned() else {
+                        self.frames.pop();
+                        return Ok(None);
+                    };
+
+                    *index += 1;
+                    (node, environment.clone())
+                }
+
+                Frame::While {
+                    node,
+                    environment,
+                    started,
+                } => {
+                    let Resumable::While {
+                        condition,
+                        body,
+                        increment,
+                    } = &**node
+                    else {
+                        unreachable!("While frames always hold a while loop.");
+                    };
+
+                    if let (true, Some(increment)) = (*started, increment) {
+                        interpreter.evaluate_in(increment, environment.clone())?;
+                    }
+
+                    *started = true;
+
+                    if !interpreter.evaluate_in(condition, environment.clone())?.is_truthy() {
+                        self.frames.pop();
+                        return Ok(None);
+                    }
+
+                    (body.clone(), environment.clone())
+                }
+
+                Frame::ForIn {
+                    node,
+                    iterator,
+                    environment,
+                } => {
+                    let Resumable::ForIn { keyword, name, body, .. } = &**node else {
+                        unreachable!("For-in frames always hold a for-in loop.");
+                    };
+
+                    let Some(value) = iterator.next(interpreter, keyword)? else {
+                        self.frames.pop();
+                        return Ok(None);
+                    };
+
+                    // Same as the interpreter, every iteration gets its own binding
+                    let mut iteration_environment = Environment::from(environment.clone());
+                    iteration_environment.define(name.lexeme.clone(), value);
+
+                    (body.clone(), Rc::new(RefCell::new(iteration_environment)))
+                }
+
+                // The part of the try statement that was running finished without unwinding
+                Frame::Try { .. } => {
+                    let Some(Frame::Try { node, environment, stage }) = self.frames.pop() else {
+                        unreachable!("Try frames are on top of the stack here.");
+                    };
+
+                    if let TryStage::Finally(pending) = stage {
+                        return pending.map_or(Ok(None), Err);
+                    }
+
+                    self.enter_finally(node, environment, None);
+                    return Ok(None);
+                }
+            };
+
+            return self.run(interpreter, &node, environment);
+        }
+
+        return Ok(None);
+    }
+
+    // Runs a statement of the body, either completely or by pushing the frames that step through it
+    fn run(
+        &mut self,
+        interpreter: &mut Interpreter,
+        node: &Rc<Resumable>,
+        environment: Rc<RefCell<Environment>>,
+    ) -> Result<Option<Interpretable>, RuntimeEvent> {
+        match &**node {
+            Resumable::Simple(statement) => {
+                interpreter.execute_in(std::slice::from_ref(statement), environment)?;
+            }
+
+            Resumable::Yield(value) => return Ok(Some(interpreter.evaluate_in(value, environment)?)),
+
+            Resumable::Block(_) => self.frames.push(Frame::Block {
+                block: node.clone(),
+                index: 0,
+                environment: Rc::new(RefCell::new(Environment::from(environment))),
+            }),
+
+            Resumable::If {
+                condition,
+                then_branch,
+                else_branch,
+            } => {
+                if interpreter.evaluate_in(condition, environment.clone())?.is_truthy() {
+                    return self.run(interpreter, then_branch, environment);
+                }
+
+                if let Some(else_branch) = else_branch {
+                    return self.run(interpreter, else_branch, environment);
+                }
+            }
+
+            Resumable::While { .. } => self.frames.push(Frame::While {
+                node: node.clone(),
+                environment,
+                started: false,
+            }),
+
+            Resumable::ForIn { keyword, iterable, .. } => {
+                let iterable = interpreter.evaluate_in(iterable, environment.clone())?;
+                let iterator = LoxIterator::new(interpreter, keyword, iterable)?;
+
+                self.frames.push(Frame::ForIn {
+                    node: node.clone(),
+                    iterator,
+                    environment,
+                });
+            }
+
+            Resumable::Match {
+                keyword,
+                subject,
+                arms,
+                bodies,
+            } => {
+                let (index, arm_environment) = interpreter.select_match_arm(keyword, subject, arms, environment)?;
+                return self.run(interpreter, &bodies[index], arm_environment);
+            }
+
+            Resumable::Try { body, .. } => {
+                let body = block_frame(body, Environment::from(environment.clone()));
+
+                self.frames.push(Frame::Try {
+                    node: node.clone(),
+                    environment,
+                    stage: TryStage::Body,
+                });
+                self.frames.push(body);
+            }
+        }
+
+        return Ok(None);
+    }
+
+    // Pops frames until one deals with `event`, the same way the interpreter unwinds its own stack. Loops take
+    // a break or continue, and a try statement catches errors and runs its finally body before anything leaves it
+    fn unwind(&mut self, interpreter: &mut Interpreter, mut event: RuntimeEvent) -> Result<(), RuntimeEvent> {
+        while let Some(frame) = self.frames.pop() {
+            match frame {
+                Frame::While { .. } | Frame::ForIn { .. } if matches!(event, RuntimeEvent::Break | RuntimeEvent::Continue) => {
+                    if matches!(event, RuntimeEvent::Continue) {
+                        self.frames.push(frame);
+                    }
+
+                    return Ok(());
+                }
+
+                // Anything that unwinds out of the finally body replaces the pending event
+                Frame::Try {
+                    stage: TryStage::Finally(_),
+                    ..
+                } => {}
+
+                Frame::Try { node, environment, stage } => {
+                    let Resumable::Try { catch_clause, .. } = &*node else {
+                        unreachable!("Try frames always hold a try statement.");
+                    };
+
+                    let caught = match (&stage, catch_clause) {
+                        (TryStage::Body, Some((name, handler))) => interpreter
+                            .caught_value(&event)
+                            .map(|exception| (name.lexeme.clone(), handler.clone(), exception)),
+                        _ => None,
+                    };
+
+                    if let Some((name, handler, exception)) = caught {
+                        let mut catch_environment = Environment::from(environment.clone());
+                        catch_environment.define(name, exception);
+
+                        self.frames.push(Frame::Try {
+                            node,
+                            environment,
+                            stage: TryStage::Catch,
+                        });
+                        self.frames.push(block_frame(&handler, catch_environment));
+                        return Ok(());
+                    }
+
+                    match self.enter_finally(node, environment, Some(event)) {
+                        Some(pending) => event = pending,
+                        None => return Ok(()),
+                    }
+                }
+
+                _ => {}
+            }
+        }
+
+        return Err(event);
+    }
+
+    // Pushes the finally body of a try statement, which hands `pending` on once it's done. Without a finally body
+    // the try statement is over and `pending` comes straight back
+    fn enter_finally(
+        &mut self,
+        node: Rc<Resumable>,
+        environment: Rc<RefCell<Environment>>,
+        pending: Option<RuntimeEvent>,
+    ) -> Option<RuntimeEvent> {
+        let Resumable::Try {
+            finally_body: Some(finally_body),
+            ..
+        } = &*node
+        else {
+            return pending;
+        };
+
+        let finally_body = block_frame(finally_body, Environment::from(environment.clone()));
+
+        self.frames.push(Frame::Try {
+            node,
+            environment,
+            stage: TryStage::Finally(pending),
+        });
+        self.frames.push(finally_body);
+
+        return None;
+    }
+}
+
+// Once the body finished, next() keeps returning nil
+fn native_next_call(interpreter: &mut Interpreter, paren: &Token, args: &mut Vec<Interpretable>) -> Result<Interpretable, RuntimeEvent> {
+    let Interpretable::Generator(generator) = &args[0] else {
+        unreachable!("Generator methods are bound to a generator.");
+    };
+
+    return Ok(generator.next(interpreter, paren)?.unwrap_or(Interpretable::Nil));
+}
+
+fn native_has_next_call(interpreter: &mut Interpreter, paren: &Token, args: &mut Vec<Interpretable>) -> Result<Interpretable, RuntimeEvent> {
+    let Interpretable::Generator(generator) = &args[0] else {
+        unreachable!("Generator methods are bound to a generator.");
+    };
+
+    return Ok(Interpretable::Boolean(generator.has_next(interpreter, paren)?));
+}
+
+// Generators are only equal to themselves
+impl PartialEq for LoxGenerator {
+    fn eq(&self, other: &Self) -> bool {
+        return std::ptr::eq(self, other);
+    }
+}
+
+impl Debug for LoxGenerator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        return write!(f, "LoxGenerator({})", self);
+    }
+}
+
+impl Display for LoxGenerator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.name {
+            Some(name) => return write!(f, "<generator {}>", name.lexeme),
+            None => return write!(f, "<generator anonymous>"),
+        }
+    }
+}
@@ -0,0 +1,130 @@
+use std::{cell::RefCell, rc::Rc};
+
+use crate::{
+    error::RuntimeEvent,
+    token::{types::TokenKind, Token},
+};
+
+use super::{
+    lox_class::LoxInstance, lox_generator::LoxGenerator, lox_range::LoxRange, Interpretable,
+    Interpreter,
+};
+
+/// Position of a `for-in` loop inside the value it iterates over.
+///
+/// Instances take part through a protocol: an optional `iterator()` method hands out the object to iterate,
+/// which then answers `hasNext()` and `next()`. Without `iterator()` the instance is used as its own iterator.
+/// Generators are iterators by themselves.
+pub enum LoxIterator {
+    // Lists are read live, so elements pushed during the loop are visited too
+    List {
+        list: Rc<RefCell<Vec<Interpretable>>>,
+        index: usize,
+    },
+    Chars {
+        chars: Vec<char>,
+        index: usize,
+    },
+    Range {
+        range: LoxRange,
+        index: usize,
+    },
+    // Maps iterate over a snapshot of their keys, in insertion order
+    Keys {
+        keys: Vec<Interpretable>,
+        index: usize,
+    },
+    Object(Rc<RefCell<LoxInstance>>),
+    Generator(Rc<LoxGenerator>),
+}
+
+impl LoxIterator {
+    pub fn new(interpreter: &mut Interpreter, token: &Token, value: Interpretable) -> Result<Self, RuntimeEvent> {
+        match value {
+            Interpretable::List(list) => return Ok(LoxIterator::List { list, index: 0 }),
+            Interpretable::String(s) => {
+                return Ok(LoxIterator::Chars {
+                    chars: s.chars().collect(),
+                    index: 0,
+                })
+            }
+            Interpretable::Range(range) => return Ok(LoxIterator::Range { range, index: 0 }),
+            Interpretable::Map(map) => {
+                return Ok(LoxIterator::Keys {
+                    keys: map.borrow().keys(),
+                    index: 0,
+                })
+            }
+            Interpretable::Generator(generator) => return Ok(LoxIterator::Generator(generator)),
+            Interpretable::Instance(instance) => {
+                if !instance.borrow().has("iterator") {
+                    return Ok(LoxIterator::Object(instance));
+                }
+
+                // The iterator may also be any other iterable value, like a list held by the instance
+                match call_method(interpreter, token, &instance, "iterator")? {
+                    Interpretable::Instance(iterator) => return Ok(LoxIterator::Object(iterator)),
+                    other => return LoxIterator::new(interpreter, token, other),
+                }
+            }
+            _ => {
+                return Err(RuntimeEvent::interpreter_error(
+                    token.clone(),
+                    &format!("Can't iterate over {}.", value),
+                ))
+            }
+        }
+    }
+
+    pub fn next(&mut self, interpreter: &mut Interpreter, token: &Token) -> Result<Option<Interpretable>, RuntimeEvent> {
+        match self {
+            LoxIterator::List { list, index } => {
+                let element = list.borrow().get(*index).cloned();
+                *index += 1;
+                return Ok(element);
+            }
+            LoxIterator::Chars { chars, index } => {
+                let element = chars.get(*index).map(|c| Interpretable::String(c.to_string()));
+                *index += 1;
+                return Ok(element);
+            }
+            LoxIterator::Range { range, index } => {
+                let element = range.get(*index).map(Interpretable::Number);
+                *index += 1;
+                return Ok(element);
+            }
+            LoxIterator::Keys { keys, index } => {
+                let element = keys.get(*index).cloned();
+                *index += 1;
+                return Ok(element);
+            }
+            LoxIterator::Object(iterator) => {
+                if !call_method(interpreter, token, iterator, "hasNext")?.is_truthy() {
+                    return Ok(None);
+                }
+
+                return Ok(Some(call_method(interpreter, token, iterator, "next")?));
+            }
+            LoxIterator::Generator(generator) => return generator.next(interpreter, token),
+        }
+    }
+}
+
+fn call_method(
+    interpreter: &mut Interpreter,
+    token: &Token,
+    instance: &Rc<RefCell<LoxInstance>>,
+    name: &str,
+) -> Result<Interpretable, RuntimeEvent> {
+    let name = Token::new(TokenKind::Identifier, name, None, token.line);
+
+    match LoxInstance::get(instance, &name)? {
+        method @ Interpretable::Callable(_) => return interpreter.call_value(method, token, Vec::new(), Vec::new()),
+        _ => {
+            return Err(RuntimeEvent::interpreter_error(
+                token.clone(),
+                &format!("Iterator property '{}' must be a method.", name.lexeme),
+            ))
+        }
+    }
+}
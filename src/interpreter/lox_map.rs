@@ -0,0 +1,170 @@
+use std::{collections::HashMap, fmt::Display};
+
+use crate::{error::RuntimeEvent, token::Token};
+
+use super::Interpretable;
+
+/// Hashable view of the values allowed as map keys.
+///
+/// Numbers hash on their bit pattern, with `-0` folded into `0` and every NaN folded into a single
+/// one, so that keys follow the same rules as `==` except that NaN can still be found again.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum MapKey {
+    String(String),
+    Number(u64),
+    Boolean(bool),
+    Nil,
+}
+
+impl MapKey {
+    pub fn from(token: &Token, value: &Interpretable) -> Result<Self, RuntimeEvent> {
+        match value {
+            Interpretable::String(s) => return Ok(MapKey::String(s.clone())),
+            Interpretable::Number(n) if *n == 0.0 => return Ok(MapKey::Number(0f64.to_bits())),
+            Interpretable::Number(n) if n.is_nan() => return Ok(MapKey::Number(f64::NAN.to_bits())),
+            Interpretable::Number(n) => return Ok(MapKey::Number(n.to_bits())),
+            Interpretable::Boolean(b) => return Ok(MapKey::Boolean(*b)),
+            Interpretable::Nil => return Ok(MapKey::Nil),
+            _ => {
+                return Err(RuntimeEvent::interpreter_error(
+                    token.clone(),
+                    &format!("Can't use {} as a map key, only numbers, strings, booleans and nil are allowed.", value),
+                ))
+            }
+        }
+    }
+
+    pub fn to_value(&self) -> Interpretable {
+        match self {
+            MapKey::String(s) => return Interpretable::String(s.clone()),
+            MapKey::Number(bits) => return Interpretable::Number(f64::from_bits(*bits)),
+            MapKey::Boolean(b) => return Interpretable::Boolean(*b),
+            MapKey::Nil => return Interpretable::Nil,
+        }
+    }
+}
+
+/// Map that remembers insertion order, so printing and iterating it is deterministic.
+#[derive(Clone, Debug)]
+pub struct LoxMap {
+    entries: Vec<(MapKey, Interpretable)>,
+    indices: HashMap<MapKey, usize>,
+}
+
+impl LoxMap {
+    pub fn new() -> Self {
+        return LoxMap {
+            entries: Vec::new(),
+            indices: HashMap::new(),
+        };
+    }
+
+    pub fn len(&self) -> usize {
+        return self.entries.len();
+    }
+
+    pub fn get(&self, key: &MapKey) -> Option<&Interpretable> {
+        return self.indices.get(key).map(|i| &self.entries[*i].1);
+    }
+
+    pub fn insert(&mut self, key: MapKey, value: Interpretable) {
+        match self.indices.get(&key) {
+            Some(i) => self.entries[*i].1 = value,
+            None => {
+                self.indices.insert(key.clone(), self.entries.len());
+                self.entries.push((key, value));
+            }
+        }
+    }
+
+    pub fn remove(&mut self, key: &MapKey) -> Option<Interpretable> {
+        let index = self.indices.remove(key)?;
+        let (_, value) = self.entries.remove(index);
+
+        // Every entry after the removed one moved back by one slot
+        for (key, _) in &self.entries[index..] {
+            *self.indices.get_mut(key).expect("Map index out of sync") -= 1;
+        }
+
+        return Some(value);
+    }
+
+    pub fn keys(&self) -> Vec<Interpretable> {
+        return self.entries.iter().map(|(key, _)| key.to_value()).collect();
+    }
+
+    pub fn values(&self) -> Vec<Interpretable> {
+        return self.entries.iter().map(|(_, value)| value.clone()).collect();
+    }
+}
+
+// Two maps are equal when they hold the same entries, regardless of insertion order
+impl PartialEq for LoxMap {
+    fn eq(&self, other: &Self) -> bool {
+        if self.len() != other.len() {
+            return false;
+        }
+
+        return self.entries.iter().all(|(key, value)| other.get(key) == Some(value));
+    }
+}
+
+impl Display for LoxMap {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{{")?;
+        for (i, (key, value)) in self.entries.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}: {}", key.to_value(), value)?;
+        }
+        write!(f, "}}")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::token::types::TokenKind;
+
+    use super::*;
+
+    fn key(value: Interpretable) -> Result<MapKey, RuntimeEvent> {
+        let token = Token::new(TokenKind::RightBracket, "]", None, 1);
+        return MapKey::from(&token, &value);
+    }
+
+    #[test]
+    fn should_fold_signed_zeros_into_one_key() {
+        assert_eq!(key(Interpretable::Number(0.0)).unwrap(), key(Interpretable::Number(-0.0)).unwrap());
+    }
+
+    #[test]
+    fn should_find_nan_key_again() {
+        let mut map = LoxMap::new();
+        map.insert(key(Interpretable::Number(f64::NAN)).unwrap(), Interpretable::Boolean(true));
+
+        assert_eq!(map.get(&key(Interpretable::Number(-f64::NAN)).unwrap()), Some(&Interpretable::Boolean(true)));
+    }
+
+    #[test]
+    fn should_keep_insertion_order_after_remove() {
+        let mut map = LoxMap::new();
+        for name in ["a", "b", "c"] {
+            map.insert(key(Interpretable::String(name.to_string())).unwrap(), Interpretable::Nil);
+        }
+
+        map.remove(&key(Interpretable::String("a".to_string())).unwrap());
+        map.insert(key(Interpretable::String("a".to_string())).unwrap(), Interpretable::Nil);
+
+        let expected: Vec<Interpretable> = ["b", "c", "a"].iter().map(|s| Interpretable::String(s.to_string())).collect();
+        assert_eq!(map.keys(), expected);
+        assert_eq!(map.get(&key(Interpretable::String("c".to_string())).unwrap()), Some(&Interpretable::Nil));
+    }
+
+    #[test]
+    fn should_reject_unhashable_keys() {
+        let list = Interpretable::List(std::rc::Rc::new(std::cell::RefCell::new(vec![])));
+
+        assert!(key(list).is_err());
+    }
+}
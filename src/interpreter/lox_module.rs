@@ -0,0 +1,52 @@
+use std::{cell::RefCell, fmt::Display, rc::Rc};
+
+use crate::{error::RuntimeEvent, token::Token};
+
+use super::{Environment, Interpretable};
+
+/// Namespace value bound by an import, exposing the top-level names a module declares.
+///
+/// Every module runs in its own global environment, which stays alive here so functions declared in the module
+/// keep resolving its globals after the import finished.
+#[derive(Debug)]
+pub struct LoxModule {
+    pub name: String,
+    globals: Rc<RefCell<Environment>>,
+}
+
+impl LoxModule {
+    pub fn new(name: &str, globals: Rc<RefCell<Environment>>) -> Self {
+        return LoxModule {
+            name: name.to_string(),
+            globals,
+        };
+    }
+
+    pub fn get(&self, name: &Token) -> Result<Interpretable, RuntimeEvent> {
+        let globals = self.globals.borrow();
+
+        // Only names the module declares itself are members, not the natives every module gets
+        match globals.values.get(name) {
+            Some(value) if !globals.values.is_native(name) => return Ok(value.clone()),
+            _ => {
+                return Err(RuntimeEvent::interpreter_error(
+                    name.clone(),
+                    &format!("Module '{}' has no member '{}'.", self.name, name.lexeme),
+                ))
+            }
+        }
+    }
+}
+
+// Each file is loaded once, so two namespaces are equal only when they come from the same import
+impl PartialEq for LoxModule {
+    fn eq(&self, other: &Self) -> bool {
+        return std::ptr::eq(self, other);
+    }
+}
+
+impl Display for LoxModule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        return write!(f, "<module {}>", self.name);
+    }
+}
@@ -0,0 +1,59 @@
+use std::fmt::Display;
+
+use crate::{error::RuntimeEvent, token::Token};
+
+/// Numeric range built by `start..end` or `start..=end`, counting from `start` towards `end` by `step`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LoxRange {
+    start: f64,
+    end: f64,
+    step: f64,
+    inclusive: bool,
+}
+
+impl LoxRange {
+    pub fn new(start: f64, end: f64, inclusive: bool) -> Self {
+        return LoxRange {
+            start,
+            end,
+            step: 1.0,
+            inclusive,
+        };
+    }
+
+    // A negative step counts down, so `10..0 step -1` goes from 10 to 1
+    pub fn with_step(&self, token: &Token, step: f64) -> Result<Self, RuntimeEvent> {
+        if step == 0.0 {
+            return Err(RuntimeEvent::interpreter_error(token.clone(), "Range step can't be zero."));
+        }
+
+        return Ok(LoxRange { step, ..*self });
+    }
+
+    /// Value at `index` steps from the start, or `None` once it went past the end. Multiplying instead of
+    /// adding the step each time keeps fractional steps from drifting.
+    pub fn get(&self, index: usize) -> Option<f64> {
+        let value = self.start + self.step * index as f64;
+
+        let in_range = match (self.step > 0.0, self.inclusive) {
+            (true, true) => value <= self.end,
+            (true, false) => value < self.end,
+            (false, true) => value >= self.end,
+            (false, false) => value > self.end,
+        };
+
+        return if in_range { Some(value) } else { None };
+    }
+}
+
+impl Display for LoxRange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}{}{}", self.start, if self.inclusive { "..=" } else { ".." }, self.end)?;
+
+        if self.step != 1.0 {
+            write!(f, " step {}", self.step)?;
+        }
+
+        return Ok(());
+    }
+}
@@ -1,6 +1,15 @@
 pub mod environment;
 pub mod interpreter;
 pub mod lox_callable;
+pub mod lox_class;
+pub mod lox_enum;
+pub mod lox_generator;
+pub mod lox_iterator;
+pub mod lox_map;
+pub mod lox_module;
+pub mod lox_range;
+pub mod natives;
+pub mod recursion_guard;
 
 pub use environment::Environment;
 pub use interpreter::Interpretable;
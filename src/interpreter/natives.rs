@@ -0,0 +1,191 @@
+use std::{
+    cell::RefCell,
+    rc::Rc,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crate::{error::RuntimeEvent, token::Token};
+
+use super::{
+    lox_map::{LoxMap, MapKey},
+    Interpretable, Interpreter,
+};
+
+type ListRef = Rc<RefCell<Vec<Interpretable>>>;
+type MapRef = Rc<RefCell<LoxMap>>;
+
+/// Converts a Lox value into a position inside a list of `length` elements.
+pub fn list_index(token: &Token, index: &Interpretable, length: usize) -> Result<usize, RuntimeEvent> {
+    match index {
+        Interpretable::Number(n) if n.fract() != 0.0 => {
+            return Err(RuntimeEvent::interpreter_error(token.clone(), "List index must be an integer."));
+        }
+
+        Interpretable::Number(n) if *n >= 0.0 && (*n as usize) < length => return Ok(*n as usize),
+
+        Interpretable::Number(n) => {
+            return Err(RuntimeEvent::interpreter_error(
+                token.clone(),
+                &format!("List index {} out of range for length {}.", n, length),
+            ));
+        }
+
+        _ => return Err(RuntimeEvent::interpreter_error(token.clone(), "List index must be a number.")),
+    }
+}
+
+fn expect_list(paren: &Token, value: &Interpretable, function_name: &str) -> Result<ListRef, RuntimeEvent> {
+    match value {
+        Interpretable::List(list) => return Ok(list.clone()),
+        _ => {
+            return Err(RuntimeEvent::interpreter_error(
+                paren.clone(),
+                &format!("{}() expects a list as its first argument.", function_name),
+            ))
+        }
+    }
+}
+
+fn expect_map(paren: &Token, value: &Interpretable, function_name: &str) -> Result<MapRef, RuntimeEvent> {
+    match value {
+        Interpretable::Map(map) => return Ok(map.clone()),
+        _ => {
+            return Err(RuntimeEvent::interpreter_error(
+                paren.clone(),
+                &format!("{}() expects a map as its first argument.", function_name),
+            ))
+        }
+    }
+}
+
+pub fn native_clock_call(_interpreter: &mut Interpreter, _paren: &Token, _args: &mut Vec<Interpretable>) -> Result<Interpretable, RuntimeEvent> {
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).expect("Time went backwards!");
+    return Ok(Interpretable::Number((now.as_millis() / 1000) as f64));
+}
+
+pub fn native_len_call(_interpreter: &mut Interpreter, paren: &Token, args: &mut Vec<Interpretable>) -> Result<Interpretable, RuntimeEvent> {
+    match &args[0] {
+        Interpretable::List(list) => return Ok(Interpretable::Number(list.borrow().len() as f64)),
+        Interpretable::Map(map) => return Ok(Interpretable::Number(map.borrow().len() as f64)),
+        Interpretable::String(s) => return Ok(Interpretable::Number(s.chars().count() as f64)),
+        _ => {
+            return Err(RuntimeEvent::interpreter_error(
+                paren.clone(),
+                "len() expects a list, a map or a string.",
+            ))
+        }
+    }
+}
+
+pub fn native_push_call(_interpreter: &mut Interpreter, paren: &Token, args: &mut Vec<Interpretable>) -> Result<Interpretable, RuntimeEvent> {
+    let list = expect_list(paren, &args[0], "push")?;
+    list.borrow_mut().push(args[1].clone());
+
+    return Ok(Interpretable::Nil);
+}
+
+pub fn native_pop_call(_interpreter: &mut Interpreter, paren: &Token, args: &mut Vec<Interpretable>) -> Result<Interpretable, RuntimeEvent> {
+    let list = expect_list(paren, &args[0], "pop")?;
+    let popped = list.borrow_mut().pop();
+
+    match popped {
+        Some(value) => return Ok(value),
+        None => return Err(RuntimeEvent::interpreter_error(paren.clone(), "Can't pop from an empty list.")),
+    }
+}
+
+pub fn native_insert_call(_interpreter: &mut Interpreter, paren: &Token, args: &mut Vec<Interpretable>) -> Result<Interpretable, RuntimeEvent> {
+    let list = expect_list(paren, &args[0], "insert")?;
+
+    // Inserting right after the last element is allowed
+    let length = list.borrow().len();
+    let index = list_index(paren, &args[1], length + 1)?;
+
+    list.borrow_mut().insert(index, args[2].clone());
+
+    return Ok(Interpretable::Nil);
+}
+
+pub fn native_remove_call(_interpreter: &mut Interpreter, paren: &Token, args: &mut Vec<Interpretable>) -> Result<Interpretable, RuntimeEvent> {
+    let list = expect_list(paren, &args[0], "remove")?;
+
+    let length = list.borrow().len();
+    let index = list_index(paren, &args[1], length)?;
+
+    return Ok(list.borrow_mut().remove(index));
+}
+
+pub fn native_slice_call(_interpreter: &mut Interpreter, paren: &Token, args: &mut Vec<Interpretable>) -> Result<Interpretable, RuntimeEvent> {
+    let list = expect_list(paren, &args[0], "slice")?;
+
+    // Both ends may point one past the last element
+    let length = list.borrow().len();
+    let start = list_index(paren, &args[1], length + 1)?;
+    let end = list_index(paren, &args[2], length + 1)?;
+
+    if start > end {
+        return Err(RuntimeEvent::interpreter_error(
+            paren.clone(),
+            &format!("Slice start {} is greater than its end {}.", start, end),
+        ));
+    }
+
+    let sliced = list.borrow()[start..end].to_vec();
+
+    return Ok(Interpretable::List(Rc::new(RefCell::new(sliced))));
+}
+
+pub fn native_sort_call(_interpreter: &mut Interpreter, paren: &Token, args: &mut Vec<Interpretable>) -> Result<Interpretable, RuntimeEvent> {
+    let list = expect_list(paren, &args[0], "sort")?;
+    let mut elements = list.borrow_mut();
+
+    if elements.iter().all(|e| matches!(e, Interpretable::Number(_))) {
+        elements.sort_by(|a, b| match (a, b) {
+            (Interpretable::Number(x), Interpretable::Number(y)) => x.total_cmp(y),
+            _ => unreachable!(),
+        });
+    } else if elements.iter().all(|e| matches!(e, Interpretable::String(_))) {
+        elements.sort_by(|a, b| match (a, b) {
+            (Interpretable::String(x), Interpretable::String(y)) => x.cmp(y),
+            _ => unreachable!(),
+        });
+    } else {
+        return Err(RuntimeEvent::interpreter_error(
+            paren.clone(),
+            "sort() expects a list of only numbers or only strings.",
+        ));
+    }
+
+    return Ok(Interpretable::Nil);
+}
+
+pub fn native_keys_call(_interpreter: &mut Interpreter, paren: &Token, args: &mut Vec<Interpretable>) -> Result<Interpretable, RuntimeEvent> {
+    let map = expect_map(paren, &args[0], "keys")?;
+    let keys = map.borrow().keys();
+
+    return Ok(Interpretable::List(Rc::new(RefCell::new(keys))));
+}
+
+pub fn native_values_call(_interpreter: &mut Interpreter, paren: &Token, args: &mut Vec<Interpretable>) -> Result<Interpretable, RuntimeEvent> {
+    let map = expect_map(paren, &args[0], "values")?;
+    let values = map.borrow().values();
+
+    return Ok(Interpretable::List(Rc::new(RefCell::new(values))));
+}
+
+pub fn native_has_call(_interpreter: &mut Interpreter, paren: &Token, args: &mut Vec<Interpretable>) -> Result<Interpretable, RuntimeEvent> {
+    let map = expect_map(paren, &args[0], "has")?;
+    let key = MapKey::from(paren, &args[1])?;
+
+    return Ok(Interpretable::Boolean(map.borrow().get(&key).is_some()));
+}
+
+pub fn native_delete_call(_interpreter: &mut Interpreter, paren: &Token, args: &mut Vec<Interpretable>) -> Result<Interpretable, RuntimeEvent> {
+    let map = expect_map(paren, &args[0], "delete")?;
+    let key = MapKey::from(paren, &args[1])?;
+
+    // Deleting a missing key is not an error, the result tells whether anything was removed
+    let removed = map.borrow_mut().remove(&key);
+
+    return Ok(Interpretable::Boolean(removed.is_some()));
+}
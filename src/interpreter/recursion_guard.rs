@@ -0,0 +1,36 @@
+use std::cell::RefCell;
+
+// Lists and maps are shared, so one can end up inside itself. These guards keep printing and comparing such a
+// value from recursing forever, identifying values by the address of their shared contents.
+thread_local! {
+    static PRINTING: RefCell<Vec<*const ()>> = const { RefCell::new(Vec::new()) };
+    static COMPARING: RefCell<Vec<(*const (), *const ())>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Runs `print` for `value`, or returns `None` when `value` is already being printed further up so the caller
+/// can print a placeholder instead.
+pub fn printing<T>(value: *const (), print: impl FnOnce() -> T) -> Option<T> {
+    if PRINTING.with(|printing| printing.borrow().contains(&value)) {
+        return None;
+    }
+
+    PRINTING.with(|printing| printing.borrow_mut().push(value));
+    let result = print();
+    PRINTING.with(|printing| printing.borrow_mut().pop());
+
+    return Some(result);
+}
+
+/// Runs `compare` for the pair `a` and `b`. A pair met again while it is being compared counts as equal, any
+/// difference between them is found by the comparison already running further up.
+pub fn comparing(a: *const (), b: *const (), compare: impl FnOnce() -> bool) -> bool {
+    if COMPARING.with(|comparing| comparing.borrow().contains(&(a, b))) {
+        return true;
+    }
+
+    COMPARING.with(|comparing| comparing.borrow_mut().push((a, b)));
+    let result = compare();
+    COMPARING.with(|comparing| comparing.borrow_mut().pop());
+
+    return result;
+}
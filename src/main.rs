@@ -3,6 +3,7 @@ use std::io;
 use std::io::Write;
 use std::path::PathBuf;
 use std::process::ExitCode;
+use std::thread;
 
 use clap::Parser as ClapParser;
 
@@ -10,12 +11,15 @@ mod ast;
 mod error;
 mod interpreter;
 mod parser;
+mod resolver;
 mod scanner;
 mod token;
 
 use error::ErrorHandler;
+use interpreter::interpreter::DEFAULT_MAX_CALL_DEPTH;
 use interpreter::Interpreter;
 use parser::Parser;
+use resolver::Resolver;
 use scanner::Scanner;
 
 /// Rust based Lox language interpreter
@@ -24,22 +28,59 @@ struct CommandLineArguments {
     /// Lox script to run (optional)
     #[arg()]
     script: Option<PathBuf>,
+
+    /// Calls that can be nested before a "Stack overflow." runtime error, from 1 to 10000
+    #[arg(long, default_value_t = DEFAULT_MAX_CALL_DEPTH, value_parser = parse_max_call_depth)]
+    max_call_depth: usize,
+}
+
+// Native stack reserved per Lox call, calls made from deeply nested statements take more and overflow sooner
+const STACK_SIZE_PER_CALL: usize = 128 * 1024;
+const BASE_STACK_SIZE: usize = 16 * 1024 * 1024;
+
+// Keeps the stack the interpreter thread asks for within what the system can reserve, about 1.3 GiB at most
+const MAX_CALL_DEPTH_LIMIT: usize = 10_000;
+
+fn parse_max_call_depth(value: &str) -> Result<usize, String> {
+    let depth: usize = value.parse().map_err(|_| format!("'{}' isn't a number", value))?;
+
+    if !(1..=MAX_CALL_DEPTH_LIMIT).contains(&depth) {
+        return Err(format!("must be between 1 and {}", MAX_CALL_DEPTH_LIMIT));
+    }
+
+    return Ok(depth);
 }
 
 fn main() -> ExitCode {
     let args = CommandLineArguments::parse();
 
-    match &args.script {
+    // The interpreter recurses on the native stack, so it gets a thread with room for the deepest allowed call
+    let stack_size = interpreter_stack_size(args.max_call_depth);
+
+    let interpreter_thread = thread::Builder::new().stack_size(stack_size).spawn(move || match &args.script {
         Some(script_path) => {
-            return run_file(script_path);
+            return run_file(script_path, args.max_call_depth);
         }
         None => {
-            return run_prompt();
+            return run_prompt(args.max_call_depth);
+        }
+    });
+
+    match interpreter_thread {
+        Ok(handle) => return handle.join().unwrap_or(ExitCode::FAILURE),
+        Err(error) => {
+            eprintln!("Failed to start interpreter with --max-call-depth {}: {}", args.max_call_depth, error);
+
+            return ExitCode::FAILURE;
         }
     }
 }
 
-fn run_file(script_path: &PathBuf) -> ExitCode {
+fn interpreter_stack_size(max_call_depth: usize) -> usize {
+    return max_call_depth * STACK_SIZE_PER_CALL + BASE_STACK_SIZE;
+}
+
+fn run_file(script_path: &PathBuf, max_call_depth: usize) -> ExitCode {
     let content: String = fs::read_to_string(script_path).expect("Failed to read lox script");
 
     let mut error_handler = ErrorHandler::new();
@@ -53,9 +94,23 @@ fn run_file(script_path: &PathBuf) -> ExitCode {
     let mut parser = Parser::new(tokens, &mut error_handler);
     match parser.parse() {
         Ok(statements) => {
+            let mut resolver = Resolver::new(&mut error_handler);
+            resolver.resolve(&statements);
+
+            if error_handler.had_error {
+                return ExitCode::FAILURE;
+            }
+
             let mut interpreter = Interpreter::new();
+            interpreter.set_script_path(script_path);
+            interpreter.set_max_call_depth(max_call_depth);
+            interpreter.set_stack_size(interpreter_stack_size(max_call_depth));
 
             interpreter.interpret(statements);
+
+            if interpreter.had_runtime_error() {
+                return ExitCode::FAILURE;
+            }
         }
         Err(error) => eprintln!("{}", error),
     }
@@ -63,11 +118,13 @@ fn run_file(script_path: &PathBuf) -> ExitCode {
     return ExitCode::SUCCESS;
 }
 
-fn run_prompt() -> ExitCode {
+fn run_prompt(max_call_depth: usize) -> ExitCode {
     let stdin = io::stdin();
     let mut input = String::new();
 
     let mut interpreter = Interpreter::new();
+    interpreter.set_max_call_depth(max_call_depth);
+    interpreter.set_stack_size(interpreter_stack_size(max_call_depth));
 
     loop {
         print!("> ");
@@ -89,7 +146,12 @@ fn run_prompt() -> ExitCode {
                 let mut parser = Parser::new(tokens, &mut error_handler);
                 match parser.parse() {
                     Ok(statements) => {
-                        interpreter.interpret(statements);
+                        let mut resolver = Resolver::new(&mut error_handler);
+                        resolver.resolve(&statements);
+
+                        if !error_handler.had_error {
+                            interpreter.interpret(statements);
+                        }
                     }
                     Err(error) => eprintln!("{}", error),
                 }
@@ -1,6 +1,11 @@
+use std::path::Path;
+
 use crate::ast::expr::Expr;
+use crate::ast::parameter::Parameter;
+use crate::ast::pattern::{MatchArm, Pattern};
 use crate::ast::stmt::Stmt;
 use crate::error::{ProcessingErrorHandler, RuntimeEvent};
+use crate::scanner::scanner::get_keyword_token_kind;
 use crate::token::types::Literal;
 use crate::token::types::TokenKind;
 use crate::token::Token;
@@ -36,12 +41,33 @@ impl<'a, ErrorHandler: ProcessingErrorHandler> Parser<'a, ErrorHandler> {
         return Ok(statements);
     }
 
+    // The comma operator has the lowest precedence of all. Argument lists, list elements, map entries and
+    // initializers are parsed with assignment instead, so their separators are never mistaken for it
     fn expression(&mut self) -> Result<Expr, RuntimeEvent> {
-        return self.assignment();
+        let mut expr = self.assignment()?;
+
+        while self.consume(TokenKind::Comma) {
+            let operator = self.previous();
+            let right = self.assignment()?;
+
+            expr = Expr::new_binary(expr, operator, right);
+        }
+
+        return Ok(expr);
     }
 
     fn declaration(&mut self) -> Result<Stmt, RuntimeEvent> {
-        if self.consume(TokenKind::Fun) {
+        if self.consume(TokenKind::Class) {
+            return self.class_declaration();
+        }
+
+        if self.consume(TokenKind::Enum) {
+            return self.enum_declaration();
+        }
+
+        // Anonymous functions start like a declaration but are expressions
+        if self.check(TokenKind::Fun) && self.check_next(TokenKind::Identifier) {
+            self.advance();
             return self.function("function");
         }
 
@@ -49,20 +75,103 @@ impl<'a, ErrorHandler: ProcessingErrorHandler> Parser<'a, ErrorHandler> {
             return self.var_declaration();
         }
 
+        if self.consume(TokenKind::Const) {
+            return self.const_declaration();
+        }
+
         return self.statement();
     }
 
+    fn class_declaration(&mut self) -> Result<Stmt, RuntimeEvent> {
+        let name = self.consume_or(TokenKind::Identifier, "Expect class name.")?;
+
+        let mut superclass: Option<Expr> = None;
+        if self.consume(TokenKind::Less) {
+            let superclass_name = self.consume_or(TokenKind::Identifier, "Expect superclass name.")?;
+            superclass = Some(Expr::new_variable(superclass_name));
+        }
+
+        self.consume_or(TokenKind::LeftBrace, "Expect '{' before class body.")?;
+
+        let mut methods: Vec<Stmt> = Vec::new();
+        while !self.check(TokenKind::RightBrace) && !self.is_at_end() {
+            methods.push(self.function("method")?);
+        }
+
+        self.consume_or(TokenKind::RightBrace, "Expect '}' after class body.")?;
+
+        return Ok(Stmt::new_class_stmt(name, superclass, methods));
+    }
+
+    fn enum_declaration(&mut self) -> Result<Stmt, RuntimeEvent> {
+        let name = self.consume_or(TokenKind::Identifier, "Expect enum name.")?;
+        self.consume_or(TokenKind::LeftBrace, "Expect '{' before enum body.")?;
+
+        let mut variants: Vec<(Token, Vec<Token>)> = Vec::new();
+        while !self.check(TokenKind::RightBrace) && !self.is_at_end() {
+            let variant = self.consume_or(TokenKind::Identifier, "Expect variant name.")?;
+            if variants.iter().any(|(other, _)| other.lexeme == variant.lexeme) {
+                return Err(self.error(&variant, "Already a variant with this name in this enum."));
+            }
+
+            let mut fields: Vec<Token> = Vec::new();
+            if self.consume(TokenKind::LeftParen) {
+                if !self.check(TokenKind::RightParen) {
+                    loop {
+                        let field = self.consume_or(TokenKind::Identifier, "Expect field name.")?;
+                        if fields.iter().any(|other| other.lexeme == field.lexeme) {
+                            return Err(self.error(&field, "Already a field with this name in this variant."));
+                        }
+
+                        fields.push(field);
+
+                        if !self.consume(TokenKind::Comma) {
+                            break;
+                        }
+                    }
+                }
+
+                self.consume_or(TokenKind::RightParen, "Expect ')' after variant fields.")?;
+            }
+
+            variants.push((variant, fields));
+
+            // The comma after the last variant is optional
+            if !self.consume(TokenKind::Comma) {
+                break;
+            }
+        }
+
+        self.consume_or(TokenKind::RightBrace, "Expect '}' after enum body.")?;
+
+        return Ok(Stmt::new_enum_stmt(name, variants));
+    }
+
     fn statement(&mut self) -> Result<Stmt, RuntimeEvent> {
-        if self.consume(TokenKind::For) {
+        if self.consume(TokenKind::Break) {
+            return self.break_statement();
+        } else if self.consume(TokenKind::Continue) {
+            return self.continue_statement();
+        } else if self.consume(TokenKind::For) {
             return self.for_statement();
         } else if self.consume(TokenKind::If) {
             return self.if_statement();
+        } else if self.consume(TokenKind::Import) {
+            return self.import_statement();
+        } else if self.consume(TokenKind::Match) {
+            return self.match_statement();
         } else if self.consume(TokenKind::Print) {
             return self.print_statement();
         } else if self.consume(TokenKind::Return) {
             return self.return_statement();
+        } else if self.consume(TokenKind::Throw) {
+            return self.throw_statement();
+        } else if self.consume(TokenKind::Try) {
+            return self.try_statement();
         } else if self.consume(TokenKind::While) {
             return self.while_statement();
+        } else if self.consume(TokenKind::Yield) {
+            return self.yield_statement();
         } else if self.consume(TokenKind::LeftBrace) {
             let declarations = self.block()?;
 
@@ -72,9 +181,36 @@ impl<'a, ErrorHandler: ProcessingErrorHandler> Parser<'a, ErrorHandler> {
         return self.expression_statement();
     }
 
+    fn break_statement(&mut self) -> Result<Stmt, RuntimeEvent> {
+        let keyword = self.previous();
+
+        self.consume_or(TokenKind::Semicolon, "Expect ';' after 'break'.")?;
+
+        return Ok(Stmt::new_break_stmt(keyword));
+    }
+
+    fn continue_statement(&mut self) -> Result<Stmt, RuntimeEvent> {
+        let keyword = self.previous();
+
+        self.consume_or(TokenKind::Semicolon, "Expect ';' after 'continue'.")?;
+
+        return Ok(Stmt::new_continue_stmt(keyword));
+    }
+
     fn for_statement(&mut self) -> Result<Stmt, RuntimeEvent> {
+        let keyword = self.previous();
         self.consume_or(TokenKind::LeftParen, "Expect '(' after 'for'.")?;
 
+        // 'in' is only a keyword right after the loop variable, elsewhere it stays a valid name
+        let is_for_in = self
+            .tokens
+            .get(self.current + 1)
+            .is_some_and(|token| token.kind == TokenKind::Identifier && token.lexeme == "in");
+
+        if self.check(TokenKind::Identifier) && is_for_in {
+            return self.for_in_statement(keyword);
+        }
+
         let initializer: Option<Stmt>;
         if self.consume(TokenKind::Semicolon) {
             initializer = None;
@@ -100,12 +236,9 @@ impl<'a, ErrorHandler: ProcessingErrorHandler> Parser<'a, ErrorHandler> {
 
         let mut body = self.statement()?;
 
-        if let Some(increment) = increment {
-            body = Stmt::new_block_stmt(vec![body, Stmt::new_expr_stmt(increment)]);
-        }
-
+        // The increment is kept apart from the body so that 'continue' still runs it
         let condition = condition.unwrap_or_else(|| Expr::new_literal(Literal::Boolean(true)));
-        body = Stmt::new_while_stmt(condition, body);
+        body = Stmt::new_while_stmt(condition, body, increment);
 
         if let Some(initializer) = initializer {
             body = Stmt::new_block_stmt(vec![initializer, body]);
@@ -114,6 +247,18 @@ impl<'a, ErrorHandler: ProcessingErrorHandler> Parser<'a, ErrorHandler> {
         return Ok(body);
     }
 
+    fn for_in_statement(&mut self, keyword: Token) -> Result<Stmt, RuntimeEvent> {
+        let name = self.advance();
+        self.advance();
+
+        let iterable = self.expression()?;
+        self.consume_or(TokenKind::RightParen, "Expect ')' after for-in clause.")?;
+
+        let body = self.statement()?;
+
+        return Ok(Stmt::new_for_in_stmt(keyword, name, iterable, body));
+    }
+
     fn if_statement(&mut self) -> Result<Stmt, RuntimeEvent> {
         self.consume_or(TokenKind::LeftParen, "Expect '(' after 'if'.")?;
 
@@ -130,6 +275,225 @@ impl<'a, ErrorHandler: ProcessingErrorHandler> Parser<'a, ErrorHandler> {
         return Ok(Stmt::new_if_stmt(condition, then_branch, else_branch));
     }
 
+    // Without an 'as' clause the module is bound to the name of its file, which then has to be an identifier
+    fn import_statement(&mut self) -> Result<Stmt, RuntimeEvent> {
+        let keyword = self.previous();
+        let path_token = self.consume_or(TokenKind::String, "Expect module path after 'import'.")?;
+        let Some(Literal::String(path)) = path_token.literal.clone() else {
+            unreachable!("String tokens always carry a string literal.");
+        };
+
+        let name = if self.check(TokenKind::Identifier) && self.peek().lexeme == "as" {
+            self.advance();
+            self.consume_or(TokenKind::Identifier, "Expect module name after 'as'.")?
+        } else {
+            let stem = Path::new(&path)
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().to_string())
+                .unwrap_or_default();
+
+            if !is_identifier(&stem) {
+                return Err(self.error(&path_token, "Module file name is not an identifier, use 'as' to name it."));
+            }
+
+            Token::new(TokenKind::Identifier, &stem, None, path_token.line)
+        };
+
+        self.consume_or(TokenKind::Semicolon, "Expect ';' after import.")?;
+
+        return Ok(Stmt::new_import_stmt(keyword, path, name));
+    }
+
+    fn match_statement(&mut self) -> Result<Stmt, RuntimeEvent> {
+        let keyword = self.previous();
+
+        self.consume_or(TokenKind::LeftParen, "Expect '(' after 'match'.")?;
+        let subject = self.expression()?;
+        self.consume_or(TokenKind::RightParen, "Expect ')' after match value.")?;
+
+        self.consume_or(TokenKind::LeftBrace, "Expect '{' before match arms.")?;
+
+        let mut arms: Vec<MatchArm> = Vec::new();
+        while !self.check(TokenKind::RightBrace) && !self.is_at_end() {
+            arms.push(self.match_arm()?);
+        }
+
+        self.consume_or(TokenKind::RightBrace, "Expect '}' after match arms.")?;
+
+        return Ok(Stmt::new_match_stmt(keyword, subject, arms));
+    }
+
+    fn match_arm(&mut self) -> Result<MatchArm, RuntimeEvent> {
+        let mut patterns = vec![self.pattern()?];
+        while self.consume(TokenKind::Pipe) {
+            patterns.push(self.pattern()?);
+        }
+
+        // With several patterns it would be unclear which one the names come from
+        if patterns.len() > 1 {
+            let binding_variant = patterns.iter().find_map(|pattern| match pattern {
+                Pattern::Variant {
+                    variant, fields: Some(_), ..
+                } => Some(variant.clone()),
+                _ => None,
+            });
+
+            if let Some(variant) = binding_variant {
+                return Err(self.error(&variant, "Can't bind fields in an arm with several patterns."));
+            }
+        }
+
+        let mut guard: Option<Expr> = None;
+        if self.consume(TokenKind::If) {
+            guard = Some(self.expression()?);
+        }
+
+        self.consume_or(TokenKind::EqualGreater, "Expect '=>' after match pattern.")?;
+        let body = self.match_arm_body()?;
+
+        return Ok(MatchArm::new(patterns, guard, body));
+    }
+
+    // Arms may be separated by commas. A print or expression body can end with ';', ',' or the closing brace, its
+    // value is parsed without the comma operator so `1 => print "one", 2 => print "two"` keeps two arms
+    fn match_arm_body(&mut self) -> Result<Stmt, RuntimeEvent> {
+        let is_print = self.consume(TokenKind::Print);
+
+        let is_other_statement = matches!(
+            self.peek().kind,
+            TokenKind::Break
+                | TokenKind::Continue
+                | TokenKind::For
+                | TokenKind::If
+                | TokenKind::Import
+                | TokenKind::Match
+                | TokenKind::Return
+                | TokenKind::Throw
+                | TokenKind::Try
+                | TokenKind::While
+                | TokenKind::Yield
+                | TokenKind::LeftBrace
+        );
+
+        if !is_print && is_other_statement {
+            let body = self.statement()?;
+            self.consume(TokenKind::Comma);
+
+            return Ok(body);
+        }
+
+        let value = self.assignment()?;
+
+        if !self.consume_if_one_of(vec![TokenKind::Semicolon, TokenKind::Comma]) && !self.check(TokenKind::RightBrace) {
+            let message = if is_print {
+                "Expect ';' or ',' after value."
+            } else {
+                "Expect ';' or ',' after expression."
+            };
+
+            return Err(self.error(&self.peek(), message));
+        }
+
+        if is_print {
+            return Ok(Stmt::new_print_stmt(value));
+        }
+
+        return Ok(Stmt::new_expr_stmt(value));
+    }
+
+    fn pattern(&mut self) -> Result<Pattern, RuntimeEvent> {
+        if self.check(TokenKind::Identifier) && self.peek().lexeme == "_" {
+            self.advance();
+            return Ok(Pattern::Wildcard);
+        }
+
+        if self.consume(TokenKind::Identifier) {
+            return self.variant_pattern();
+        }
+
+        let start = self.pattern_literal()?;
+
+        if self.consume_if_one_of(vec![TokenKind::DotDot, TokenKind::DotDotEqual]) {
+            let operator = self.previous();
+            let end = self.pattern_literal()?;
+
+            match (start, end) {
+                (Literal::Number(start), Literal::Number(end)) => {
+                    return Ok(Pattern::Range {
+                        start,
+                        end,
+                        inclusive: operator.kind == TokenKind::DotDotEqual,
+                    });
+                }
+                _ => return Err(self.error(&operator, "Range pattern bounds must be numbers.")),
+            }
+        }
+
+        return Ok(Pattern::Literal(start));
+    }
+
+    fn variant_pattern(&mut self) -> Result<Pattern, RuntimeEvent> {
+        let enum_name = Expr::new_variable(self.previous());
+        self.consume_or(TokenKind::Dot, "Expect '.' after enum name in pattern.")?;
+        let variant = self.consume_or(TokenKind::Identifier, "Expect variant name after '.'.")?;
+
+        if !self.consume(TokenKind::LeftParen) {
+            return Ok(Pattern::Variant {
+                enum_name,
+                variant,
+                fields: None,
+            });
+        }
+
+        let mut fields: Vec<Token> = Vec::new();
+        if !self.check(TokenKind::RightParen) {
+            loop {
+                fields.push(self.consume_or(TokenKind::Identifier, "Expect field name in pattern.")?);
+
+                if !self.consume(TokenKind::Comma) {
+                    break;
+                }
+            }
+        }
+
+        self.consume_or(TokenKind::RightParen, "Expect ')' after pattern fields.")?;
+
+        return Ok(Pattern::Variant {
+            enum_name,
+            variant,
+            fields: Some(fields),
+        });
+    }
+
+    fn pattern_literal(&mut self) -> Result<Literal, RuntimeEvent> {
+        if self.consume(TokenKind::Minus) {
+            let number = self.consume_or(TokenKind::Number, "Expect number after '-' in pattern.")?;
+            let Some(Literal::Number(value)) = number.literal else {
+                unreachable!("Number tokens always carry a number literal.");
+            };
+
+            return Ok(Literal::Number(-value));
+        }
+
+        if self.consume_if_one_of(vec![TokenKind::Number, TokenKind::String]) {
+            return Ok(self.previous().literal.expect("Literal tokens always carry a value."));
+        }
+
+        if self.consume(TokenKind::True) {
+            return Ok(Literal::Boolean(true));
+        }
+
+        if self.consume(TokenKind::False) {
+            return Ok(Literal::Boolean(false));
+        }
+
+        if self.consume(TokenKind::Nil) {
+            return Ok(Literal::Nil);
+        }
+
+        return Err(self.error(&self.peek(), "Expect literal, range or '_' as match pattern."));
+    }
+
     fn print_statement(&mut self) -> Result<Stmt, RuntimeEvent> {
         let expr = self.expression()?;
 
@@ -156,7 +520,7 @@ impl<'a, ErrorHandler: ProcessingErrorHandler> Parser<'a, ErrorHandler> {
 
         let mut initializer: Option<Expr> = None;
         if self.consume(TokenKind::Equal) {
-            initializer = Some(self.expression()?);
+            initializer = Some(self.assignment()?);
         }
 
         self.consume_or(TokenKind::Semicolon, "Expect ';' after variable declaration.")?;
@@ -164,6 +528,17 @@ impl<'a, ErrorHandler: ProcessingErrorHandler> Parser<'a, ErrorHandler> {
         return Ok(Stmt::new_var_stmt(name, initializer));
     }
 
+    fn const_declaration(&mut self) -> Result<Stmt, RuntimeEvent> {
+        let name = self.consume_or(TokenKind::Identifier, "Expect constant name.")?;
+
+        self.consume_or(TokenKind::Equal, "Expect '=' after constant name.")?;
+        let initializer = self.assignment()?;
+
+        self.consume_or(TokenKind::Semicolon, "Expect ';' after constant declaration.")?;
+
+        return Ok(Stmt::new_const_stmt(name, initializer));
+    }
+
     fn while_statement(&mut self) -> Result<Stmt, RuntimeEvent> {
         self.consume_or(TokenKind::LeftParen, "Expect '(' after 'while'.")?;
 
@@ -173,7 +548,56 @@ impl<'a, ErrorHandler: ProcessingErrorHandler> Parser<'a, ErrorHandler> {
 
         let body = self.statement()?;
 
-        return Ok(Stmt::new_while_stmt(condition, body));
+        return Ok(Stmt::new_while_stmt(condition, body, None));
+    }
+
+    fn throw_statement(&mut self) -> Result<Stmt, RuntimeEvent> {
+        let keyword = self.previous();
+        let value = self.expression()?;
+
+        self.consume_or(TokenKind::Semicolon, "Expect ';' after thrown value.")?;
+
+        return Ok(Stmt::new_throw_stmt(keyword, value));
+    }
+
+    fn yield_statement(&mut self) -> Result<Stmt, RuntimeEvent> {
+        let keyword = self.previous();
+
+        let mut value = Expr::new_literal(Literal::Nil);
+        if !self.check(TokenKind::Semicolon) {
+            value = self.expression()?;
+        }
+
+        self.consume_or(TokenKind::Semicolon, "Expect ';' after yielded value.")?;
+
+        return Ok(Stmt::new_yield_stmt(keyword, value));
+    }
+
+    fn try_statement(&mut self) -> Result<Stmt, RuntimeEvent> {
+        self.consume_or(TokenKind::LeftBrace, "Expect '{' after 'try'.")?;
+        let body = self.block()?;
+
+        let mut catch_clause: Option<(Token, Vec<Stmt>)> = None;
+        if self.consume(TokenKind::Catch) {
+            self.consume_or(TokenKind::LeftParen, "Expect '(' after 'catch'.")?;
+            let name = self.consume_or(TokenKind::Identifier, "Expect exception variable name.")?;
+            self.consume_or(TokenKind::RightParen, "Expect ')' after exception variable name.")?;
+
+            self.consume_or(TokenKind::LeftBrace, "Expect '{' before catch body.")?;
+            catch_clause = Some((name, self.block()?));
+        }
+
+        let mut finally_body: Option<Vec<Stmt>> = None;
+        if self.consume(TokenKind::Finally) {
+            self.consume_or(TokenKind::LeftBrace, "Expect '{' after 'finally'.")?;
+            finally_body = Some(self.block()?);
+        }
+
+        if catch_clause.is_none() && finally_body.is_none() {
+            return Err(self.error(&self.peek(), "Expect 'catch' or 'finally' after try block."));
+        }
+
+        return Ok(Stmt::new_try_stmt(body, catch_clause, finally_body));
     }
 
     fn block(&mut self) -> Result<Vec<Stmt>, RuntimeEvent> {
@@ -201,7 +625,24 @@ impl<'a, ErrorHandler: ProcessingErrorHandler> Parser<'a, ErrorHandler> {
 
         self.consume_or(TokenKind::LeftParen, &format!("Expect '(' after {} name.", function_kind))?;
 
-        let mut parameters: Vec<Token> = Vec::new();
+        let (parameters, body) = self.function_body(function_kind)?;
+
+        return Ok(Stmt::new_function(name, parameters, body));
+    }
+
+    fn lambda(&mut self) -> Result<Expr, RuntimeEvent> {
+        let keyword = self.previous();
+
+        self.consume_or(TokenKind::LeftParen, "Expect '(' after 'fun'.")?;
+
+        let (parameters, body) = self.function_body("function")?;
+
+        return Ok(Expr::new_lambda(keyword, parameters, body));
+    }
+
+    // Parses everything after the opening parenthesis of a function
+    fn function_body(&mut self, function_kind: &str) -> Result<(Vec<Parameter>, Vec<Stmt>), RuntimeEvent> {
+        let mut parameters: Vec<Parameter> = Vec::new();
 
         if !self.check(TokenKind::RightParen) {
             loop {
@@ -209,7 +650,7 @@ impl<'a, ErrorHandler: ProcessingErrorHandler> Parser<'a, ErrorHandler> {
                     self.error(&self.peek(), "Can't have more than 255 parameters");
                 }
 
-                parameters.push(self.consume_or(TokenKind::Identifier, "Expect parameter name.")?);
+                parameters.push(self.parameter(&parameters)?);
 
                 if !self.consume(TokenKind::Comma) {
                     break;
@@ -222,29 +663,90 @@ impl<'a, ErrorHandler: ProcessingErrorHandler> Parser<'a, ErrorHandler> {
 
         let body = self.block()?;
 
-        return Ok(Stmt::new_function(name, parameters, body));
+        return Ok((parameters, body));
+    }
+
+    // Parameters with a default can only be followed by other defaults or the rest parameter, which comes last
+    fn parameter(&mut self, previous: &Vec<Parameter>) -> Result<Parameter, RuntimeEvent> {
+        if previous.last().is_some_and(|parameter| parameter.is_rest) {
+            return Err(self.error(&self.peek(), "Rest parameter must be the last parameter."));
+        }
+
+        if self.consume(TokenKind::DotDotDot) {
+            let name = self.consume_or(TokenKind::Identifier, "Expect rest parameter name after '...'.")?;
+            return Ok(Parameter::new_rest(name));
+        }
+
+        let name = self.consume_or(TokenKind::Identifier, "Expect parameter name.")?;
+
+        if self.consume(TokenKind::Equal) {
+            let default = self.assignment()?;
+            return Ok(Parameter::new(name, Some(default)));
+        }
+
+        if previous.iter().any(|parameter| parameter.default.is_some()) {
+            return Err(self.error(&name, "Parameter without a default can't follow one with a default."));
+        }
+
+        return Ok(Parameter::new(name, None));
     }
 
     fn assignment(&mut self) -> Result<Expr, RuntimeEvent> {
-        let expr = self.or()?;
+        let expr = self.conditional()?;
 
         if self.consume(TokenKind::Equal) {
             let equals = self.previous();
             let value = self.assignment()?;
 
             match expr {
-                Expr::Variable { name } => {
+                Expr::Variable { name, depth: _ } => {
                     return Ok(Expr::new_assignment(name, value));
                 }
+                Expr::Get { object, name } => {
+                    return Ok(Expr::new_set(*object, name, value));
+                }
+                Expr::Index { object, bracket, index } => {
+                    return Ok(Expr::new_index_set(*object, bracket, *index, value));
+                }
                 _ => {
                     return Err(self.error(&equals, "Invalid assignment target."));
                 }
             }
         }
 
+        if self.consume_if_one_of(vec![
+            TokenKind::PlusEqual,
+            TokenKind::MinusEqual,
+            TokenKind::StarEqual,
+            TokenKind::SlashEqual,
+        ]) {
+            let operator = self.previous();
+            let value = self.assignment()?;
+
+            if !is_assignable(&expr) {
+                return Err(self.error(&operator, "Invalid assignment target."));
+            }
+
+            return Ok(Expr::new_compound_assignment(expr, operator, value));
+        }
+
         return Ok(expr);
     }
 
+    fn conditional(&mut self) -> Result<Expr, RuntimeEvent> {
+        let condition = self.or()?;
+
+        if self.consume(TokenKind::Question) {
+            let then_branch = self.expression()?;
+            self.consume_or(TokenKind::Colon, "Expect ':' after then branch of conditional expression.")?;
+            let else_branch = self.conditional()?;
+
+            return Ok(Expr::new_conditional(condition, then_branch, else_branch));
+        }
+
+        return Ok(condition);
+    }
+
     fn or(&mut self) -> Result<Expr, RuntimeEvent> {
         let mut expr = self.and()?;
 
@@ -312,6 +814,13 @@ impl<'a, ErrorHandler: ProcessingErrorHandler> Parser<'a, ErrorHandler> {
         }
     }
 
+    fn check_next(&mut self, candidate: TokenKind) -> bool {
+        match self.tokens.get(self.current + 1) {
+            Some(token) => return token.kind == candidate,
+            None => return false,
+        }
+    }
+
     fn advance(&mut self) -> Token {
         if !self.is_at_end() {
             self.current = self.current + 1;
@@ -333,7 +842,7 @@ impl<'a, ErrorHandler: ProcessingErrorHandler> Parser<'a, ErrorHandler> {
     }
 
     fn comparison(&mut self) -> Result<Expr, RuntimeEvent> {
-        let mut expr = self.term()?;
+        let mut expr = self.range()?;
 
         while self.consume_if_one_of(vec![
             TokenKind::Greater,
@@ -342,7 +851,7 @@ impl<'a, ErrorHandler: ProcessingErrorHandler> Parser<'a, ErrorHandler> {
             TokenKind::LessEqual,
         ]) {
             let operator = self.previous();
-            let right = self.term()?;
+            let right = self.range()?;
 
             expr = Expr::new_binary(expr, operator, right);
         }
@@ -350,6 +859,27 @@ impl<'a, ErrorHandler: ProcessingErrorHandler> Parser<'a, ErrorHandler> {
         return Ok(expr);
     }
 
+    // Ranges don't chain, `0..n - 1` already reads as the whole range. 'step' is only a keyword right after
+    // the end of a range
+    fn range(&mut self) -> Result<Expr, RuntimeEvent> {
+        let expr = self.term()?;
+
+        if self.consume_if_one_of(vec![TokenKind::DotDot, TokenKind::DotDotEqual]) {
+            let operator = self.previous();
+            let end = self.term()?;
+
+            let mut step: Option<Expr> = None;
+            if self.check(TokenKind::Identifier) && self.peek().lexeme == "step" {
+                self.advance();
+                step = Some(self.term()?);
+            }
+
+            return Ok(Expr::new_range(expr, operator, end, step));
+        }
+
+        return Ok(expr);
+    }
+
     fn term(&mut self) -> Result<Expr, RuntimeEvent> {
         let mut expr = self.factor()?;
 
@@ -366,7 +896,12 @@ impl<'a, ErrorHandler: ProcessingErrorHandler> Parser<'a, ErrorHandler> {
     fn factor(&mut self) -> Result<Expr, RuntimeEvent> {
         let mut expr = self.unary()?;
 
-        while self.consume_if_one_of(vec![TokenKind::Slash, TokenKind::Star]) {
+        while self.consume_if_one_of(vec![
+            TokenKind::Slash,
+            TokenKind::Star,
+            TokenKind::Percent,
+            TokenKind::TildeSlash,
+        ]) {
             let operator = self.previous();
             let right = self.unary()?;
 
@@ -384,19 +919,76 @@ impl<'a, ErrorHandler: ProcessingErrorHandler> Parser<'a, ErrorHandler> {
             return Ok(Expr::new_unary(operator, right));
         }
 
-        return self.call();
+        if self.consume_if_one_of(vec![TokenKind::PlusPlus, TokenKind::MinusMinus]) {
+            let operator = self.previous();
+            let target = self.unary()?;
+
+            if !is_assignable(&target) {
+                return Err(self.error(&operator, "Invalid assignment target."));
+            }
+
+            return Ok(Expr::new_increment(target, operator, true));
+        }
+
+        return self.power();
+    }
+
+    // Binds tighter than a unary operator on its left, so -2 ** 2 is -(2 ** 2). The right operand goes back
+    // through unary, which makes the operator right-associative and still allows 2 ** -1
+    fn power(&mut self) -> Result<Expr, RuntimeEvent> {
+        let expr = self.postfix()?;
+
+        if self.consume(TokenKind::StarStar) {
+            let operator = self.previous();
+            let right = self.unary()?;
+
+            return Ok(Expr::new_binary(expr, operator, right));
+        }
+
+        return Ok(expr);
+    }
+
+    fn postfix(&mut self) -> Result<Expr, RuntimeEvent> {
+        let mut expr = self.call()?;
+
+        while self.consume_if_one_of(vec![TokenKind::PlusPlus, TokenKind::MinusMinus]) {
+            let operator = self.previous();
+
+            if !is_assignable(&expr) {
+                return Err(self.error(&operator, "Invalid assignment target."));
+            }
+
+            expr = Expr::new_increment(expr, operator, false);
+        }
+
+        return Ok(expr);
     }
 
+    // Named arguments are written `name: value` and have to come after all the positional ones
     fn finish_call(&mut self, callee: Expr) -> Result<Expr, RuntimeEvent> {
         let mut arguments: Vec<Expr> = Vec::new();
+        let mut named_arguments: Vec<(Token, Expr)> = Vec::new();
 
         if !self.check(TokenKind::RightParen) {
             loop {
-                if arguments.len() >= 255 {
+                if arguments.len() + named_arguments.len() >= 255 {
                     return Err(self.error(&self.peek(), "Can't have more then 255 arguments!"));
                 }
 
-                arguments.push(self.expression()?);
+                if self.check(TokenKind::Identifier) && self.check_next(TokenKind::Colon) {
+                    let name = self.advance();
+                    self.advance();
+
+                    if named_arguments.iter().any(|(named, _)| named.lexeme == name.lexeme) {
+                        return Err(self.error(&name, &format!("Duplicate named argument '{}'.", name.lexeme)));
+                    }
+
+                    named_arguments.push((name, self.assignment()?));
+                } else if !named_arguments.is_empty() {
+                    return Err(self.error(&self.peek(), "Positional argument can't follow a named argument."));
+                } else {
+                    arguments.push(self.assignment()?);
+                }
 
                 if !self.consume(TokenKind::Comma) {
                     break;
@@ -406,7 +998,67 @@ impl<'a, ErrorHandler: ProcessingErrorHandler> Parser<'a, ErrorHandler> {
 
         let paren: Token = self.consume_or(TokenKind::RightParen, "Expect ')' after arguments.")?;
 
-        return Ok(Expr::new_call(callee, paren, arguments));
+        return Ok(Expr::new_call(callee, paren, arguments, named_arguments));
+    }
+
+    // The scanner splits "a ${x} b" into a segment token, the tokens of x and a closing string token
+    fn interpolation(&mut self) -> Result<Expr, RuntimeEvent> {
+        let mut parts: Vec<Expr> = vec![Expr::new_literal(self.previous().literal.unwrap())];
+
+        loop {
+            parts.push(self.expression()?);
+
+            if self.consume(TokenKind::StringSegment) {
+                parts.push(Expr::new_literal(self.previous().literal.unwrap()));
+            } else {
+                let end = self.consume_or(TokenKind::String, "Expect '}' after interpolated expression.")?;
+                parts.push(Expr::new_literal(end.literal.unwrap()));
+
+                break;
+            }
+        }
+
+        return Ok(Expr::new_interpolation(parts));
+    }
+
+    fn list(&mut self) -> Result<Expr, RuntimeEvent> {
+        let mut elements: Vec<Expr> = Vec::new();
+
+        if !self.check(TokenKind::RightBracket) {
+            loop {
+                elements.push(self.assignment()?);
+
+                if !self.consume(TokenKind::Comma) {
+                    break;
+                }
+            }
+        }
+
+        self.consume_or(TokenKind::RightBracket, "Expect ']' after list elements.")?;
+
+        return Ok(Expr::new_list(elements));
+    }
+
+    fn map(&mut self) -> Result<Expr, RuntimeEvent> {
+        let mut entries: Vec<(Expr, Expr)> = Vec::new();
+
+        if !self.check(TokenKind::RightBrace) {
+            loop {
+                let key = self.assignment()?;
+                self.consume_or(TokenKind::Colon, "Expect ':' after map key.")?;
+                let value = self.assignment()?;
+
+                entries.push((key, value));
+
+                if !self.consume(TokenKind::Comma) {
+                    break;
+                }
+            }
+        }
+
+        let brace = self.consume_or(TokenKind::RightBrace, "Expect '}' after map entries.")?;
+
+        return Ok(Expr::new_map(brace, entries));
     }
 
     fn call(&mut self) -> Result<Expr, RuntimeEvent> {
@@ -415,6 +1067,13 @@ impl<'a, ErrorHandler: ProcessingErrorHandler> Parser<'a, ErrorHandler> {
         loop {
             if self.consume(TokenKind::LeftParen) {
                 expr = self.finish_call(expr)?;
+            } else if self.consume(TokenKind::Dot) {
+                let name = self.consume_or(TokenKind::Identifier, "Expect property name after '.'.")?;
+                expr = Expr::new_get(expr, name);
+            } else if self.consume(TokenKind::LeftBracket) {
+                let index = self.expression()?;
+                let bracket = self.consume_or(TokenKind::RightBracket, "Expect ']' after index.")?;
+                expr = Expr::new_index(expr, bracket, index);
             } else {
                 break;
             }
@@ -441,12 +1100,40 @@ impl<'a, ErrorHandler: ProcessingErrorHandler> Parser<'a, ErrorHandler> {
             return Ok(Expr::new_literal(prev.literal.unwrap()));
         }
 
+        if self.consume(TokenKind::StringSegment) {
+            return self.interpolation();
+        }
+
+        if self.consume(TokenKind::Fun) {
+            return self.lambda();
+        }
+
+        if self.consume(TokenKind::LeftBracket) {
+            return self.list();
+        }
+
+        if self.consume(TokenKind::LeftBrace) {
+            return self.map();
+        }
+
         if self.consume(TokenKind::LeftParen) {
             let expr = self.expression().ok();
             self.consume_or(TokenKind::RightParen, "Expect ')' after expression.")?;
             return Ok(Expr::new_grouping(expr.unwrap()));
         }
 
+        if self.consume(TokenKind::Super) {
+            let keyword = self.previous();
+            self.consume_or(TokenKind::Dot, "Expect '.' after 'super'.")?;
+            let method = self.consume_or(TokenKind::Identifier, "Expect superclass method name.")?;
+
+            return Ok(Expr::new_super(keyword, method));
+        }
+
+        if self.consume(TokenKind::This) {
+            return Ok(Expr::new_this(self.previous()));
+        }
+
         if self.consume(TokenKind::Identifier) {
             return Ok(Expr::new_variable(self.previous()));
         }
@@ -485,13 +1172,20 @@ impl<'a, ErrorHandler: ProcessingErrorHandler> Parser<'a, ErrorHandler> {
             } else {
                 match self.peek().kind {
                     TokenKind::Class
+                    | TokenKind::Enum
                     | TokenKind::Fun
                     | TokenKind::Var
+                    | TokenKind::Const
                     | TokenKind::For
                     | TokenKind::If
                     | TokenKind::While
                     | TokenKind::Print
-                    | TokenKind::Return => {
+                    | TokenKind::Return
+                    | TokenKind::Throw
+                    | TokenKind::Try
+                    | TokenKind::Yield
+                    | TokenKind::Import
+                    | TokenKind::Match => {
                         return;
                     }
 
@@ -504,6 +1198,23 @@ impl<'a, ErrorHandler: ProcessingErrorHandler> Parser<'a, ErrorHandler> {
     }
 }
 
+fn is_identifier(text: &str) -> bool {
+    let mut chars = text.chars();
+
+    let starts_like_identifier = match chars.next() {
+        Some(first) => first.is_ascii_alphabetic() || first == '_',
+        None => false,
+    };
+
+    return starts_like_identifier
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+        && get_keyword_token_kind(text).is_none();
+}
+
+fn is_assignable(expr: &Expr) -> bool {
+    return matches!(expr, Expr::Variable { .. } | Expr::Get { .. } | Expr::Index { .. });
+}
+
 #[cfg(test)]
 mod test {
     use crate::error::ProcessingErrorHandler;
@@ -532,6 +1243,82 @@ mod test {
         }
     }
 
+    fn parse_expression(source: &str) -> Expr {
+        let mut error_handler = ErrorSpy::new();
+        let mut scanner = Scanner::new(source, &mut error_handler);
+
+        let tokens = scanner.scan_tokens();
+        let mut parser = Parser::new(tokens, &mut error_handler);
+
+        match &parser.parse().expect("Parsing failed")[0] {
+            Stmt::ExprStmt { expr } => return *expr.clone(),
+            _ => panic!("Got non-expression statement"),
+        }
+    }
+
+    #[test]
+    fn should_parse_power_as_right_associative() {
+        assert_eq!(parse_expression("2 ** 3 ** 2;").to_string(), "(** 2 (** 3 2))");
+    }
+
+    #[test]
+    fn should_parse_power_tighter_than_unary() {
+        assert_eq!(parse_expression("-2 ** -2;").to_string(), "(- (** 2 (- 2)))");
+    }
+
+    #[test]
+    fn should_parse_remainder_and_floor_division_as_factors() {
+        assert_eq!(parse_expression("1 + 7 % 4 ~/ 2;").to_string(), "(+ 1 (~/ (% 7 4) 2))");
+    }
+
+    #[test]
+    fn should_parse_prefix_and_postfix_increments() {
+        assert_eq!(parse_expression("-++a - b--;").to_string(), "(- (- (++ a)) (b --))");
+    }
+
+    #[test]
+    fn should_parse_compound_assignment_as_right_associative() {
+        assert_eq!(parse_expression("a += b *= 2;").to_string(), "a += b *= 2");
+    }
+
+    #[test]
+    fn should_parse_conditional_as_right_associative() {
+        assert_eq!(parse_expression("a ? b : c ? d : e;").to_string(), "(?: a b (?: c d e))");
+    }
+
+    #[test]
+    fn should_parse_conditional_looser_than_logical_operators() {
+        assert_eq!(parse_expression("a or b ? 1 + 2 : 3;").to_string(), "(?: a or b (+ 1 2) 3)");
+    }
+
+    #[test]
+    fn should_parse_comma_as_left_associative() {
+        assert_eq!(parse_expression("a = 1, b = 2, 3;").to_string(), "(, (, a = 1 b = 2) 3)");
+    }
+
+    #[test]
+    fn should_not_parse_arguments_as_comma_expressions() {
+        assert_eq!(parse_expression("f(1, (2, 3));").to_string(), "(call f [1, (group (, 2 3))])");
+    }
+
+    #[test]
+    fn should_parse_named_arguments_after_positional_ones() {
+        assert_eq!(
+            parse_expression("connect(1, host: \"x\", port: a ? 80 : 8080);").to_string(),
+            "(call connect [1, host: \"x\", port: (?: a 80 8080)])"
+        );
+    }
+
+    #[test]
+    fn should_parse_range_looser_than_arithmetic() {
+        assert_eq!(parse_expression("0..n - 1 < 2;").to_string(), "(< (.. 0 (- n 1)) 2)");
+    }
+
+    #[test]
+    fn should_parse_range_step() {
+        assert_eq!(parse_expression("10..=0 step -2;").to_string(), "(..= 10 0 step (- 2))");
+    }
+
     #[test]
     fn should_parse_expression() {
         let mut error_handler = ErrorSpy::new();
@@ -0,0 +1,685 @@
+use std::cell::Cell;
+use std::collections::{HashMap, HashSet};
+
+use crate::ast::{Expr, ExprVisitor, MatchArm, Parameter, Pattern, Stmt, StmtVisitor};
+use crate::error::ProcessingErrorHandler;
+use crate::token::types::Literal;
+use crate::token::Token;
+
+#[derive(Clone, Copy, PartialEq)]
+enum FunctionType {
+    None,
+    Function,
+    Method,
+    Initializer,
+    Generator,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum ClassType {
+    None,
+    Class,
+    Subclass,
+}
+
+/// Static pass run between parsing and interpreting, binding every local variable use to the number of
+/// scopes between it and its declaration. Anything left unresolved is assumed to be a global.
+pub struct Resolver<'a, ErrorHandler: ProcessingErrorHandler> {
+    scopes: Vec<HashMap<String, bool>>,
+    // Names declared with `const`, one set per entry of `scopes` plus one for the globals
+    constant_scopes: Vec<HashSet<String>>,
+    global_constants: HashSet<String>,
+    current_function: FunctionType,
+    current_class: ClassType,
+    loop_depth: usize,
+    error_handler: &'a mut ErrorHandler,
+}
+
+impl<'a, ErrorHandler: ProcessingErrorHandler> Resolver<'a, ErrorHandler> {
+    pub fn new(error_handler: &'a mut ErrorHandler) -> Self {
+        return Resolver {
+            scopes: Vec::new(),
+            constant_scopes: Vec::new(),
+            global_constants: HashSet::new(),
+            current_function: FunctionType::None,
+            current_class: ClassType::None,
+            loop_depth: 0,
+            error_handler: error_handler,
+        };
+    }
+
+    pub fn resolve(&mut self, statements: &Vec<Stmt>) {
+        for statement in statements {
+            self.resolve_stmt(statement);
+        }
+    }
+
+    fn resolve_stmt(&mut self, statement: &Stmt) {
+        statement.accept(self);
+    }
+
+    fn resolve_expr(&mut self, expression: &Expr) {
+        expression.accept(self);
+    }
+
+    fn begin_scope(&mut self) {
+        self.scopes.push(HashMap::new());
+        self.constant_scopes.push(HashSet::new());
+    }
+
+    fn end_scope(&mut self) {
+        self.scopes.pop();
+        self.constant_scopes.pop();
+    }
+
+    fn declare(&mut self, name: &Token) {
+        let Some(scope) = self.scopes.last_mut() else {
+            return;
+        };
+
+        if scope.contains_key(&name.lexeme) {
+            self.error(name, "Already a variable with this name in this scope.");
+            return;
+        }
+
+        scope.insert(name.lexeme.clone(), false);
+    }
+
+    fn define(&mut self, name: &Token) {
+        if let Some(scope) = self.scopes.last_mut() {
+            scope.insert(name.lexeme.clone(), true);
+        }
+    }
+
+    fn define_constant(&mut self, name: &Token) {
+        self.define(name);
+
+        match self.constant_scopes.last_mut() {
+            Some(constants) => constants.insert(name.lexeme.clone()),
+            None => self.global_constants.insert(name.lexeme.clone()),
+        };
+    }
+
+    // Only the declaration the name resolves to matters, so a variable may shadow an outer constant
+    fn check_assignable(&mut self, name: &Token) {
+        let mut scopes = self.scopes.iter().zip(self.constant_scopes.iter()).rev();
+        let is_constant = match scopes.find(|(scope, _)| scope.contains_key(&name.lexeme)) {
+            Some((_, constants)) => constants.contains(&name.lexeme),
+            None => self.global_constants.contains(&name.lexeme),
+        };
+
+        if is_constant {
+            self.error(name, &format!("Can't assign to constant '{}'.", name.lexeme));
+        }
+    }
+
+    fn check_assignable_target(&mut self, target: &Expr) {
+        if let Expr::Variable { name, depth: _ } = target {
+            self.check_assignable(name);
+        }
+    }
+
+    fn resolve_local(&mut self, name: &Token, depth: &Cell<Option<usize>>) {
+        for (distance, scope) in self.scopes.iter().rev().enumerate() {
+            if scope.contains_key(&name.lexeme) {
+                depth.set(Some(distance));
+                return;
+            }
+        }
+    }
+
+    fn resolve_function(&mut self, parameters: &Vec<Parameter>, body: &Vec<Stmt>, function_type: FunctionType) {
+        let enclosing_function = self.current_function;
+        self.current_function = function_type;
+
+        // Initializers can't be generators, a yield in one is reported by the yield itself
+        if function_type != FunctionType::Initializer && body.iter().any(Stmt::contains_yield) {
+            self.current_function = FunctionType::Generator;
+        }
+
+        // Loops don't extend into function bodies declared inside them
+        let enclosing_loop_depth = self.loop_depth;
+        self.loop_depth = 0;
+
+        self.begin_scope();
+
+        // A default is resolved before its own parameter is declared, so it can only see the parameters before it
+        for param in parameters {
+            if let Some(default) = &param.default {
+                self.resolve_expr(default);
+            }
+
+            self.declare(&param.name);
+            self.define(&param.name);
+        }
+
+        self.resolve(body);
+
+        self.end_scope();
+
+        self.current_function = enclosing_function;
+        self.loop_depth = enclosing_loop_depth;
+    }
+
+    fn error(&mut self, token: &Token, message: &str) {
+        self.error_handler
+            .parsing_error(token.line, &format!(" at '{}'", token.lexeme), message);
+    }
+}
+
+impl<'a, ErrorHandler: ProcessingErrorHandler> ExprVisitor<()> for Resolver<'a, ErrorHandler> {
+    fn visit_binary_expr(&mut self, left: &Expr, _operator: &Token, right: &Expr) {
+        self.resolve_expr(left);
+        self.resolve_expr(right);
+    }
+
+    fn visit_call_expr(&mut self, callee: &Expr, _paren: &Token, arguments: &Vec<Expr>, named_arguments: &Vec<(Token, Expr)>) {
+        self.resolve_expr(callee);
+
+        for argument in arguments {
+            self.resolve_expr(argument);
+        }
+
+        for (_, argument) in named_arguments {
+            self.resolve_expr(argument);
+        }
+    }
+
+    fn visit_grouping_expr(&mut self, expression: &Expr) {
+        self.resolve_expr(expression);
+    }
+
+    fn visit_literal_expr(&mut self, _value: &Literal) {}
+
+    fn visit_unary_expr(&mut self, _operator: &Token, right: &Expr) {
+        self.resolve_expr(right);
+    }
+
+    fn visit_variable_expr(&mut self, name: &Token, depth: &Cell<Option<usize>>) {
+        if let Some(scope) = self.scopes.last() {
+            if scope.get(&name.lexeme) == Some(&false) {
+                self.error(name, "Can't read local variable in its own initializer.");
+            }
+        }
+
+        self.resolve_local(name, depth);
+    }
+
+    fn visit_assignment_expr(&mut self, name: &Token, value: &Expr, depth: &Cell<Option<usize>>) {
+        self.resolve_expr(value);
+        self.check_assignable(name);
+        self.resolve_local(name, depth);
+    }
+
+    fn visit_conditional_expr(&mut self, condition: &Expr, then_branch: &Expr, else_branch: &Expr) {
+        self.resolve_expr(condition);
+        self.resolve_expr(then_branch);
+        self.resolve_expr(else_branch);
+    }
+
+    fn visit_range_expr(&mut self, start: &Expr, _operator: &Token, end: &Expr, step: &Option<Box<Expr>>) {
+        self.resolve_expr(start);
+        self.resolve_expr(end);
+
+        if let Some(step) = step {
+            self.resolve_expr(step);
+        }
+    }
+
+    fn visit_logical_expr(&mut self, left: &Expr, _operator: &Token, right: &Expr) {
+        self.resolve_expr(left);
+        self.resolve_expr(right);
+    }
+
+    fn visit_get_expr(&mut self, object: &Expr, _name: &Token) {
+        self.resolve_expr(object);
+    }
+
+    fn visit_set_expr(&mut self, object: &Expr, _name: &Token, value: &Expr) {
+        self.resolve_expr(value);
+        self.resolve_expr(object);
+    }
+
+    fn visit_this_expr(&mut self, keyword: &Token, depth: &Cell<Option<usize>>) {
+        if self.current_class == ClassType::None {
+            self.error(keyword, "Can't use 'this' outside of a class.");
+            return;
+        }
+
+        self.resolve_local(keyword, depth);
+    }
+
+    fn visit_super_expr(&mut self, keyword: &Token, _method: &Token, depth: &Cell<Option<usize>>) {
+        match self.current_class {
+            ClassType::None => self.error(keyword, "Can't use 'super' outside of a class."),
+            ClassType::Class => self.error(keyword, "Can't use 'super' in a class with no superclass."),
+            ClassType::Subclass => self.resolve_local(keyword, depth),
+        }
+    }
+
+    fn visit_list_expr(&mut self, elements: &Vec<Expr>) {
+        for element in elements {
+            self.resolve_expr(element);
+        }
+    }
+
+    fn visit_interpolation_expr(&mut self, parts: &Vec<Expr>) {
+        for part in parts {
+            self.resolve_expr(part);
+        }
+    }
+
+    fn visit_lambda_expr(&mut self, _keyword: &Token, parameters: &Vec<Parameter>, body: &Vec<Stmt>) {
+        self.resolve_function(parameters, body, FunctionType::Function);
+    }
+
+    fn visit_map_expr(&mut self, _brace: &Token, entries: &Vec<(Expr, Expr)>) {
+        for (key, value) in entries {
+            self.resolve_expr(key);
+            self.resolve_expr(value);
+        }
+    }
+
+    fn visit_index_expr(&mut self, object: &Expr, _bracket: &Token, index: &Expr) {
+        self.resolve_expr(object);
+        self.resolve_expr(index);
+    }
+
+    fn visit_index_set_expr(&mut self, object: &Expr, _bracket: &Token, index: &Expr, value: &Expr) {
+        self.resolve_expr(value);
+        self.resolve_expr(object);
+        self.resolve_expr(index);
+    }
+
+    fn visit_compound_assignment_expr(&mut self, target: &Expr, _operator: &Token, value: &Expr) {
+        self.resolve_expr(value);
+        self.check_assignable_target(target);
+        self.resolve_expr(target);
+    }
+
+    fn visit_increment_expr(&mut self, target: &Expr, _operator: &Token, _is_prefix: bool) {
+        self.check_assignable_target(target);
+        self.resolve_expr(target);
+    }
+}
+
+impl<'a, ErrorHandler: ProcessingErrorHandler> StmtVisitor<()> for Resolver<'a, ErrorHandler> {
+    fn visit_expr_stmt(&mut self, expr: &Expr) {
+        self.resolve_expr(expr);
+    }
+
+    fn visit_if_stmt(&mut self, condition: &Expr, then_branch: &Stmt, else_branch: &Option<Stmt>) {
+        self.resolve_expr(condition);
+        self.resolve_stmt(then_branch);
+
+        if let Some(else_stmt) = else_branch {
+            self.resolve_stmt(else_stmt);
+        }
+    }
+
+    fn visit_print_stmt(&mut self, expr: &Expr) {
+        self.resolve_expr(expr);
+    }
+
+    fn visit_var_stmt(&mut self, name: &Token, initializer: &Option<Expr>) {
+        self.declare(name);
+
+        if let Some(initializer) = initializer {
+            self.resolve_expr(initializer);
+        }
+
+        self.define(name);
+    }
+
+    fn visit_const_stmt(&mut self, name: &Token, initializer: &Expr) {
+        self.declare(name);
+        self.resolve_expr(initializer);
+        self.define_constant(name);
+    }
+
+    fn visit_for_in_stmt(&mut self, _keyword: &Token, name: &Token, iterable: &Expr, body: &Stmt) {
+        self.resolve_expr(iterable);
+
+        self.begin_scope();
+        self.declare(name);
+        self.define(name);
+
+        self.loop_depth += 1;
+        self.resolve_stmt(body);
+        self.loop_depth -= 1;
+
+        self.end_scope();
+    }
+
+    fn visit_block_stmt(&mut self, declarations: &Vec<Stmt>) {
+        self.begin_scope();
+        self.resolve(declarations);
+        self.end_scope();
+    }
+
+    fn visit_while_stmt(&mut self, condition: &Expr, body: &Stmt, increment: &Option<Box<Expr>>) {
+        self.resolve_expr(condition);
+
+        self.loop_depth += 1;
+        self.resolve_stmt(body);
+        self.loop_depth -= 1;
+
+        if let Some(increment) = increment {
+            self.resolve_expr(increment);
+        }
+    }
+
+    fn visit_function_stmt(&mut self, name: &Token, parameters: &Vec<Parameter>, body: &Vec<Stmt>) {
+        // Defined before resolving the body so the function can refer to itself recursively
+        self.declare(name);
+        self.define(name);
+
+        self.resolve_function(parameters, body, FunctionType::Function);
+    }
+
+    fn visit_return_stmt(&mut self, keyword: &Token, value: &Expr) {
+        if self.current_function == FunctionType::None {
+            self.error(keyword, "Can't return from top-level code.");
+        }
+
+        if *value != Expr::Nil {
+            if self.current_function == FunctionType::Initializer {
+                self.error(keyword, "Can't return a value from an initializer.");
+            }
+
+            if self.current_function == FunctionType::Generator {
+                self.error(keyword, "Can't return a value from a generator.");
+            }
+
+            self.resolve_expr(value);
+        }
+    }
+
+    fn visit_class_stmt(&mut self, name: &Token, superclass: &Option<Expr>, methods: &Vec<Stmt>) {
+        let enclosing_class = self.current_class;
+        self.current_class = ClassType::Class;
+
+        self.declare(name);
+        self.define(name);
+
+        if let Some(superclass) = superclass {
+            self.current_class = ClassType::Subclass;
+            self.resolve_expr(superclass);
+
+            self.begin_scope();
+            self.scopes.last_mut().unwrap().insert(String::from("super"), true);
+        }
+
+        self.begin_scope();
+        self.scopes.last_mut().unwrap().insert(String::from("this"), true);
+
+        for method in methods {
+            if let Stmt::FunctionStmt { name, parameters, body } = method {
+                let function_type = if name.lexeme == "init" {
+                    FunctionType::Initializer
+                } else {
+                    FunctionType::Method
+                };
+
+                self.resolve_function(parameters, body, function_type);
+            }
+        }
+
+        self.end_scope();
+
+        if superclass.is_some() {
+            self.end_scope();
+        }
+
+        self.current_class = enclosing_class;
+    }
+
+    fn visit_enum_stmt(&mut self, name: &Token, _variants: &Vec<(Token, Vec<Token>)>) {
+        self.declare(name);
+        self.define(name);
+    }
+
+    fn visit_break_stmt(&mut self, keyword: &Token) {
+        if self.loop_depth == 0 {
+            self.error(keyword, "Can't use 'break' outside of a loop.");
+        }
+    }
+
+    fn visit_continue_stmt(&mut self, keyword: &Token) {
+        if self.loop_depth == 0 {
+            self.error(keyword, "Can't use 'continue' outside of a loop.");
+        }
+    }
+
+    fn visit_match_stmt(&mut self, _keyword: &Token, subject: &Expr, arms: &Vec<MatchArm>) {
+        self.resolve_expr(subject);
+
+        for arm in arms {
+            for pattern in &arm.patterns {
+                if let Pattern::Variant { enum_name, .. } = pattern {
+                    self.resolve_expr(enum_name);
+                }
+            }
+
+            // Same as the interpreter, only arms that bind fields get a scope of their own
+            let bindings = arm.bindings();
+            if let Some(bindings) = bindings {
+                self.begin_scope();
+                for name in bindings.iter().filter(|name| name.lexeme != "_") {
+                    self.declare(name);
+                    self.define(name);
+                }
+            }
+
+            if let Some(guard) = &arm.guard {
+                self.resolve_expr(guard);
+            }
+
+            self.resolve_stmt(&arm.body);
+
+            if bindings.is_some() {
+                self.end_scope();
+            }
+        }
+    }
+
+    fn visit_import_stmt(&mut self, _keyword: &Token, _path: &str, name: &Token) {
+        self.declare(name);
+        self.define(name);
+    }
+
+    fn visit_throw_stmt(&mut self, _keyword: &Token, value: &Expr) {
+        self.resolve_expr(value);
+    }
+
+    fn visit_yield_stmt(&mut self, keyword: &Token, value: &Expr) {
+        match self.current_function {
+            FunctionType::None => self.error(keyword, "Can't yield from top-level code."),
+            FunctionType::Initializer => self.error(keyword, "Can't yield from an initializer."),
+            _ => {}
+        }
+
+        self.resolve_expr(value);
+    }
+
+    fn visit_try_stmt(&mut self, body: &Vec<Stmt>, catch_clause: &Option<(Token, Vec<Stmt>)>, finally_body: &Option<Vec<Stmt>>) {
+        self.visit_block_stmt(body);
+
+        // The exception variable lives in the same scope as the catch body
+        if let Some((name, handler)) = catch_clause {
+            self.begin_scope();
+            self.declare(name);
+            self.define(name);
+            self.resolve(handler);
+            self.end_scope();
+        }
+
+        if let Some(finally_body) = finally_body {
+            self.visit_block_stmt(finally_body);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::error::ProcessingErrorHandler;
+    use crate::parser::Parser;
+    use crate::scanner::Scanner;
+
+    use super::*;
+
+    #[derive(Debug, PartialEq)]
+    struct ErrorSpy {
+        had_error: bool,
+        message: String,
+    }
+
+    impl ErrorSpy {
+        fn new() -> Self {
+            return ErrorSpy {
+                had_error: false,
+                message: "".to_string(),
+            };
+        }
+    }
+
+    impl ProcessingErrorHandler for ErrorSpy {
+        fn scanning_error(&mut self, _line: u32, message: &str) {
+            panic!("scanning_error: {}", message);
+        }
+
+        fn parsing_error(&mut self, _line: u32, _location: &str, message: &str) {
+            self.had_error = true;
+            self.message = message.to_string();
+        }
+    }
+
+    fn resolve(source: &str, error_handler: &mut ErrorSpy) -> Vec<Stmt> {
+        let mut scanner = Scanner::new(source, error_handler);
+        let tokens = scanner.scan_tokens();
+
+        let mut parser = Parser::new(tokens, error_handler);
+        let statements = parser.parse().expect("Parsing failed");
+
+        let mut resolver = Resolver::new(error_handler);
+        resolver.resolve(&statements);
+
+        return statements;
+    }
+
+    fn printed_depth(statement: &Stmt) -> Option<usize> {
+        match statement {
+            Stmt::PrintStmt { expr } => match &**expr {
+                Expr::Variable { name: _, depth } => return depth.get(),
+                _ => panic!("Expected variable expression"),
+            },
+            _ => panic!("Expected print statement"),
+        }
+    }
+
+    #[test]
+    fn should_leave_globals_unresolved() {
+        let mut error_handler = ErrorSpy::new();
+        let statements = resolve("var a = 1; print a;", &mut error_handler);
+
+        assert_eq!(printed_depth(&statements[1]), None);
+        assert_eq!(error_handler.had_error, false);
+    }
+
+    #[test]
+    fn should_resolve_local_scope_distance() {
+        let mut error_handler = ErrorSpy::new();
+        let statements = resolve("{ var a = 1; { print a; } print a; }", &mut error_handler);
+
+        match &statements[0] {
+            Stmt::BlockStmt { declarations } => {
+                match &declarations[1] {
+                    Stmt::BlockStmt { declarations: inner } => assert_eq!(printed_depth(&inner[0]), Some(1)),
+                    _ => panic!("Expected inner block"),
+                }
+
+                assert_eq!(printed_depth(&declarations[2]), Some(0));
+            }
+            _ => panic!("Expected block statement"),
+        }
+
+        assert_eq!(error_handler.had_error, false);
+    }
+
+    #[test]
+    fn should_report_this_outside_class() {
+        let mut error_handler = ErrorSpy::new();
+        resolve("print this;", &mut error_handler);
+
+        assert_eq!(error_handler.had_error, true);
+        assert_eq!(error_handler.message, "Can't use 'this' outside of a class.");
+    }
+
+    #[test]
+    fn should_report_top_level_return() {
+        let mut error_handler = ErrorSpy::new();
+        resolve("return 1;", &mut error_handler);
+
+        assert_eq!(error_handler.had_error, true);
+        assert_eq!(error_handler.message, "Can't return from top-level code.");
+    }
+
+    #[test]
+    fn should_report_self_referencing_initializer() {
+        let mut error_handler = ErrorSpy::new();
+        resolve("var a = 1; { var a = a; }", &mut error_handler);
+
+        assert_eq!(error_handler.had_error, true);
+        assert_eq!(error_handler.message, "Can't read local variable in its own initializer.");
+    }
+
+    #[test]
+    fn should_report_duplicate_local() {
+        let mut error_handler = ErrorSpy::new();
+        resolve("fun f() { var a = 1; var a = 2; }", &mut error_handler);
+
+        assert_eq!(error_handler.had_error, true);
+        assert_eq!(error_handler.message, "Already a variable with this name in this scope.");
+    }
+
+    #[test]
+    fn should_allow_global_redeclaration() {
+        let mut error_handler = ErrorSpy::new();
+        resolve("var a = 1; var a = a;", &mut error_handler);
+
+        assert_eq!(error_handler.had_error, false);
+    }
+
+    #[test]
+    fn should_allow_yield_inside_try() {
+        let mut error_handler = ErrorSpy::new();
+        resolve("fun f() { try { yield 1; } catch (e) { yield 2; } finally { yield 3; } }", &mut error_handler);
+
+        assert_eq!(error_handler.had_error, false);
+    }
+
+    #[test]
+    fn should_allow_yield_in_function_declared_inside_try() {
+        let mut error_handler = ErrorSpy::new();
+        resolve("fun f() { try { fun g() { yield 1; } } catch (e) {} }", &mut error_handler);
+
+        assert_eq!(error_handler.had_error, false);
+    }
+
+    #[test]
+    fn should_report_assignment_to_constant() {
+        let mut error_handler = ErrorSpy::new();
+        resolve("fun f() { const a = 1; { a += 2; } }", &mut error_handler);
+
+        assert_eq!(error_handler.had_error, true);
+        assert_eq!(error_handler.message, "Can't assign to constant 'a'.");
+    }
+
+    #[test]
+    fn should_allow_assignment_to_variable_shadowing_constant() {
+        let mut error_handler = ErrorSpy::new();
+        resolve("const a = 1; { var a = 2; a = 3; }", &mut error_handler);
+
+        assert_eq!(error_handler.had_error, false);
+    }
+}
@@ -10,6 +10,8 @@ pub struct Scanner<'a, ErrorHandler: ProcessingErrorHandler> {
     start: usize,
     current: usize,
     line: u32,
+    // Braces opened inside each string interpolation being scanned, innermost last
+    interpolations: Vec<usize>,
     error_handler: &'a mut ErrorHandler,
 }
 
@@ -21,6 +23,7 @@ impl<'a, ErrorHandler: ProcessingErrorHandler> Scanner<'a, ErrorHandler> {
             start: 0,
             current: 0,
             line: 1,
+            interpolations: Vec::new(),
             error_handler: error_handler,
         };
     }
@@ -31,6 +34,10 @@ impl<'a, ErrorHandler: ProcessingErrorHandler> Scanner<'a, ErrorHandler> {
             self.scan_single_token();
         }
 
+        if !self.interpolations.is_empty() {
+            self.error_handler.scanning_error(self.line, "Unterminated string interpolation.");
+        }
+
         self.add_token(TokenKind::EndOfFile, None);
 
         return std::mem::take(&mut self.tokens);
@@ -77,12 +84,35 @@ impl<'a, ErrorHandler: ProcessingErrorHandler> Scanner<'a, ErrorHandler> {
     }
 
     fn get_string_literal(&mut self) {
+        let mut value = String::new();
+        let mut is_valid = true;
+
         while self.peek() != '"' && !self.is_at_end() {
-            if self.peek() == '\n' {
-                self.line = self.line + 1;
-            }
+            match self.advance() {
+                Some('\\') => match self.get_escape_sequence() {
+                    Some(decoded) => value.push(decoded),
+                    None => is_valid = false,
+                },
+                Some('$') if self.peek() == '{' => {
+                    // Emit what we have so far and scan the embedded expression as regular tokens,
+                    // the matching '}' resumes the string
+                    self.advance();
+                    self.interpolations.push(0);
+
+                    if is_valid {
+                        self.add_token(TokenKind::StringSegment, Some(Literal::String(value)));
+                    }
+                    return;
+                }
+                Some(c) => {
+                    if c == '\n' {
+                        self.line = self.line + 1;
+                    }
 
-            self.advance();
+                    value.push(c);
+                }
+                None => break,
+            }
         }
 
         if self.is_at_end() {
@@ -91,9 +121,72 @@ impl<'a, ErrorHandler: ProcessingErrorHandler> Scanner<'a, ErrorHandler> {
         }
 
         self.advance();
-        let literal: &str = &self.source[self.start + 1..self.current - 1];
 
-        self.add_token(TokenKind::String, Some(Literal::String(literal.to_string())));
+        // Bad escapes were already reported, keep scanning but don't produce a broken literal
+        if is_valid {
+            self.add_token(TokenKind::String, Some(Literal::String(value)));
+        }
+    }
+
+    // Decodes the escape sequence following a backslash inside a string literal
+    fn get_escape_sequence(&mut self) -> Option<char> {
+        if self.is_at_end() {
+            return None;
+        }
+
+        match self.advance() {
+            Some('n') => return Some('\n'),
+            Some('t') => return Some('\t'),
+            Some('r') => return Some('\r'),
+            Some('0') => return Some('\0'),
+            Some('\\') => return Some('\\'),
+            Some('"') => return Some('"'),
+            Some('$') => return Some('$'),
+            Some('u') => return self.get_unicode_escape(),
+            Some(c) => {
+                if c == '\n' {
+                    self.line = self.line + 1;
+                }
+
+                self.error_handler
+                    .scanning_error(self.line, &format!("Invalid escape sequence '\\{}'.", c.escape_default()));
+                return None;
+            }
+            None => return None,
+        }
+    }
+
+    fn get_unicode_escape(&mut self) -> Option<char> {
+        if !self.advance_if_equal('{') {
+            self.error_handler.scanning_error(self.line, "Expect '{' after '\\u'.");
+            return None;
+        }
+
+        let mut digits = String::new();
+        while self.peek().is_ascii_hexdigit() {
+            digits.push(self.advance().expect("Ran out of characters!"));
+        }
+
+        if !self.advance_if_equal('}') {
+            self.error_handler.scanning_error(self.line, "Expect '}' after unicode escape digits.");
+            return None;
+        }
+
+        if digits.is_empty() || digits.len() > 6 {
+            self.error_handler
+                .scanning_error(self.line, "Unicode escape must have between 1 and 6 hex digits.");
+            return None;
+        }
+
+        let code_point = u32::from_str_radix(&digits, 16).expect("Hex digits were already checked");
+        match char::from_u32(code_point) {
+            Some(c) => return Some(c),
+            None => {
+                self.error_handler
+                    .scanning_error(self.line, &format!("Invalid unicode code point '\\u{{{}}}'.", digits));
+                return None;
+            }
+        }
     }
 
     fn get_number_literal(&mut self) {
@@ -126,7 +219,7 @@ impl<'a, ErrorHandler: ProcessingErrorHandler> Scanner<'a, ErrorHandler> {
     }
 
     fn get_identifier(&mut self) {
-        while self.peek().is_ascii_alphanumeric() {
+        while self.peek().is_ascii_alphanumeric() || self.peek() == '_' {
             self.advance();
         }
 
@@ -141,6 +234,31 @@ impl<'a, ErrorHandler: ProcessingErrorHandler> Scanner<'a, ErrorHandler> {
         }
     }
 
+    fn skip_block_comment(&mut self) {
+        let start_line = self.line;
+        let mut depth = 1;
+
+        while depth > 0 {
+            if self.is_at_end() {
+                self.error_handler.scanning_error(start_line, "Unterminated block comment.");
+                return;
+            }
+
+            match self.advance() {
+                Some('/') if self.peek() == '*' => {
+                    self.advance();
+                    depth = depth + 1;
+                }
+                Some('*') if self.peek() == '/' => {
+                    self.advance();
+                    depth = depth - 1;
+                }
+                Some('\n') => self.line = self.line + 1,
+                _ => {}
+            }
+        }
+    }
+
     fn add_token(&mut self, kind: TokenKind, literal: Option<Literal>) {
         let text: &str;
 
@@ -159,15 +277,86 @@ impl<'a, ErrorHandler: ProcessingErrorHandler> Scanner<'a, ErrorHandler> {
         match self.advance() {
             Some('(') => self.add_token(TokenKind::LeftParen, None),
             Some(')') => self.add_token(TokenKind::RightParen, None),
-            Some('{') => self.add_token(TokenKind::LeftBrace, None),
-            Some('}') => self.add_token(TokenKind::RightBrace, None),
+            Some('{') => {
+                if let Some(open_braces) = self.interpolations.last_mut() {
+                    *open_braces = *open_braces + 1;
+                }
+
+                self.add_token(TokenKind::LeftBrace, None);
+            }
+            Some('}') => match self.interpolations.last_mut() {
+                Some(0) => {
+                    self.interpolations.pop();
+
+                    if self.tokens.last().map(|token| &token.kind) == Some(&TokenKind::StringSegment) {
+                        self.error_handler
+                            .scanning_error(self.line, "Expect expression inside string interpolation.");
+                    }
+
+                    self.get_string_literal();
+                }
+                Some(open_braces) => {
+                    *open_braces = *open_braces - 1;
+                    self.add_token(TokenKind::RightBrace, None);
+                }
+                None => self.add_token(TokenKind::RightBrace, None),
+            },
+            Some('[') => self.add_token(TokenKind::LeftBracket, None),
+            Some(']') => self.add_token(TokenKind::RightBracket, None),
+            Some(':') => self.add_token(TokenKind::Colon, None),
+            Some('?') => self.add_token(TokenKind::Question, None),
             Some(',') => self.add_token(TokenKind::Comma, None),
-            Some('.') => self.add_token(TokenKind::Dot, None),
-            Some('-') => self.add_token(TokenKind::Minus, None),
-            Some('+') => self.add_token(TokenKind::Plus, None),
+            Some('|') => self.add_token(TokenKind::Pipe, None),
+            Some('.') => {
+                if self.advance_if_equal('.') {
+                    if self.advance_if_equal('=') {
+                        self.add_token(TokenKind::DotDotEqual, None);
+                    } else if self.advance_if_equal('.') {
+                        self.add_token(TokenKind::DotDotDot, None);
+                    } else {
+                        self.add_token(TokenKind::DotDot, None);
+                    }
+                } else {
+                    self.add_token(TokenKind::Dot, None);
+                }
+            }
+            Some('-') => {
+                if self.advance_if_equal('=') {
+                    self.add_token(TokenKind::MinusEqual, None);
+                } else if self.advance_if_equal('-') {
+                    self.add_token(TokenKind::MinusMinus, None);
+                } else {
+                    self.add_token(TokenKind::Minus, None);
+                }
+            }
+            Some('+') => {
+                if self.advance_if_equal('=') {
+                    self.add_token(TokenKind::PlusEqual, None);
+                } else if self.advance_if_equal('+') {
+                    self.add_token(TokenKind::PlusPlus, None);
+                } else {
+                    self.add_token(TokenKind::Plus, None);
+                }
+            }
             Some(';') => self.add_token(TokenKind::Semicolon, None),
-            Some('*') => self.add_token(TokenKind::Star, None),
+            Some('%') => self.add_token(TokenKind::Percent, None),
 
+            Some('*') => {
+                if self.advance_if_equal('*') {
+                    self.add_token(TokenKind::StarStar, None);
+                } else if self.advance_if_equal('=') {
+                    self.add_token(TokenKind::StarEqual, None);
+                } else {
+                    self.add_token(TokenKind::Star, None);
+                }
+            }
+            Some('~') => {
+                if self.advance_if_equal('/') {
+                    self.add_token(TokenKind::TildeSlash, None);
+                } else {
+                    self.error_handler.scanning_error(self.line, "Expect '/' after '~'.");
+                }
+            }
             Some('!') => {
                 if self.advance_if_equal('=') {
                     self.add_token(TokenKind::BangEqual, None);
@@ -178,6 +367,8 @@ impl<'a, ErrorHandler: ProcessingErrorHandler> Scanner<'a, ErrorHandler> {
             Some('=') => {
                 if self.advance_if_equal('=') {
                     self.add_token(TokenKind::EqualEqual, None);
+                } else if self.advance_if_equal('>') {
+                    self.add_token(TokenKind::EqualGreater, None);
                 } else {
                     self.add_token(TokenKind::Equal, None);
                 }
@@ -201,6 +392,10 @@ impl<'a, ErrorHandler: ProcessingErrorHandler> Scanner<'a, ErrorHandler> {
                     while self.peek() != '\n' && !self.is_at_end() {
                         self.advance();
                     }
+                } else if self.advance_if_equal('*') {
+                    self.skip_block_comment();
+                } else if self.advance_if_equal('=') {
+                    self.add_token(TokenKind::SlashEqual, None);
                 } else {
                     self.add_token(TokenKind::Slash, None);
                 }
@@ -221,7 +416,7 @@ impl<'a, ErrorHandler: ProcessingErrorHandler> Scanner<'a, ErrorHandler> {
             Some(c) => {
                 if c.is_ascii_digit() {
                     self.get_number_literal();
-                } else if c.is_ascii_alphabetic() {
+                } else if c.is_ascii_alphabetic() || c == '_' {
                     self.get_identifier();
                 } else {
                     self.error_handler.scanning_error(self.line as u32, "Unexpected character");
@@ -233,24 +428,35 @@ impl<'a, ErrorHandler: ProcessingErrorHandler> Scanner<'a, ErrorHandler> {
     }
 }
 
-fn get_keyword_token_kind(key: &str) -> Option<TokenKind> {
-    const KEYWORDS: [Keyword; 16] = [
+pub fn get_keyword_token_kind(key: &str) -> Option<TokenKind> {
+    const KEYWORDS: [Keyword; 27] = [
         Keyword::new("and", TokenKind::And),
+        Keyword::new("break", TokenKind::Break),
+        Keyword::new("catch", TokenKind::Catch),
         Keyword::new("class", TokenKind::Class),
+        Keyword::new("const", TokenKind::Const),
+        Keyword::new("continue", TokenKind::Continue),
         Keyword::new("else", TokenKind::Else),
+        Keyword::new("enum", TokenKind::Enum),
         Keyword::new("false", TokenKind::False),
+        Keyword::new("finally", TokenKind::Finally),
         Keyword::new("for", TokenKind::For),
         Keyword::new("fun", TokenKind::Fun),
         Keyword::new("if", TokenKind::If),
+        Keyword::new("import", TokenKind::Import),
+        Keyword::new("match", TokenKind::Match),
         Keyword::new("nil", TokenKind::Nil),
         Keyword::new("or", TokenKind::Or),
         Keyword::new("print", TokenKind::Print),
         Keyword::new("return", TokenKind::Return),
         Keyword::new("super", TokenKind::Super),
         Keyword::new("this", TokenKind::This),
+        Keyword::new("throw", TokenKind::Throw),
         Keyword::new("true", TokenKind::True),
+        Keyword::new("try", TokenKind::Try),
         Keyword::new("var", TokenKind::Var),
         Keyword::new("while", TokenKind::While),
+        Keyword::new("yield", TokenKind::Yield),
     ];
 
     for entry in KEYWORDS {
@@ -304,6 +510,9 @@ mod test {
             TokenKindPair::new(")", TokenKind::RightParen),
             TokenKindPair::new("{", TokenKind::LeftBrace),
             TokenKindPair::new("}", TokenKind::RightBrace),
+            TokenKindPair::new("[", TokenKind::LeftBracket),
+            TokenKindPair::new("]", TokenKind::RightBracket),
+            TokenKindPair::new(":", TokenKind::Colon),
             TokenKindPair::new(",", TokenKind::Comma),
             TokenKindPair::new(".", TokenKind::Dot),
             TokenKindPair::new("-", TokenKind::Minus),
@@ -319,6 +528,23 @@ mod test {
             TokenKindPair::new(">", TokenKind::Greater),
             TokenKindPair::new(">=", TokenKind::GreaterEqual),
             TokenKindPair::new("/", TokenKind::Slash),
+            TokenKindPair::new("%", TokenKind::Percent),
+            TokenKindPair::new("?", TokenKind::Question),
+            TokenKindPair::new("|", TokenKind::Pipe),
+            TokenKindPair::new("=>", TokenKind::EqualGreater),
+            TokenKindPair::new("..", TokenKind::DotDot),
+            TokenKindPair::new("..=", TokenKind::DotDotEqual),
+            TokenKindPair::new("...", TokenKind::DotDotDot),
+            TokenKindPair::new("_", TokenKind::Identifier),
+            TokenKindPair::new("_snake_case2", TokenKind::Identifier),
+            TokenKindPair::new("**", TokenKind::StarStar),
+            TokenKindPair::new("~/", TokenKind::TildeSlash),
+            TokenKindPair::new("+=", TokenKind::PlusEqual),
+            TokenKindPair::new("++", TokenKind::PlusPlus),
+            TokenKindPair::new("-=", TokenKind::MinusEqual),
+            TokenKindPair::new("--", TokenKind::MinusMinus),
+            TokenKindPair::new("*=", TokenKind::StarEqual),
+            TokenKindPair::new("/=", TokenKind::SlashEqual),
         ];
 
         for token in expected_tokens {
@@ -361,6 +587,36 @@ mod test {
         assert_eq!(scanner.error_handler.had_error, false);
     }
 
+    #[test]
+    fn should_ignore_nested_block_comments() {
+        let mut error_spy: ErrorSpy = ErrorSpy {
+            line: 0,
+            message: "".to_string(),
+            had_error: false,
+        };
+        let mut scanner = Scanner::new("/* outer\n /* inner\n */ still outer */ *", &mut error_spy);
+        let tokens = scanner.scan_tokens();
+
+        assert_eq!(tokens[0].kind, TokenKind::Star);
+        assert_eq!(tokens[0].line, 3);
+        assert_eq!(scanner.error_handler.had_error, false);
+    }
+
+    #[test]
+    fn should_get_unterminated_block_comment_notification() {
+        let mut error_spy: ErrorSpy = ErrorSpy {
+            line: 0,
+            message: "".to_string(),
+            had_error: false,
+        };
+        let mut scanner = Scanner::new("*\n/* outer /* inner */\n\n", &mut error_spy);
+        scanner.scan_tokens();
+
+        assert_eq!(scanner.error_handler.had_error, true);
+        assert_eq!(scanner.error_handler.line, 2);
+        assert_eq!(scanner.error_handler.message, "Unterminated block comment.");
+    }
+
     #[test]
     fn should_get_string_literal() {
         let mut error_spy: ErrorSpy = ErrorSpy {
@@ -378,6 +634,103 @@ mod test {
         assert_eq!(scanner.error_handler.had_error, false);
     }
 
+    #[test]
+    fn should_decode_escape_sequences() {
+        let mut error_spy: ErrorSpy = ErrorSpy {
+            line: 0,
+            message: "".to_string(),
+            had_error: false,
+        };
+
+        let mut scanner = Scanner::new(r#""a\n\t\r\\\"\0\u{41}\u{1F431}""#, &mut error_spy);
+        let tokens = scanner.scan_tokens();
+
+        assert_eq!(tokens[0].kind, TokenKind::String);
+        assert_eq!(tokens[0].literal, Some(Literal::String("a\n\t\r\\\"\0A🐱".to_string())));
+        assert_eq!(scanner.error_handler.had_error, false);
+    }
+
+    #[test]
+    fn should_get_invalid_escape_notification() {
+        let mut error_spy: ErrorSpy = ErrorSpy {
+            line: 0,
+            message: "".to_string(),
+            had_error: false,
+        };
+
+        let mut scanner = Scanner::new("\n\"\\q\"", &mut error_spy);
+        scanner.scan_tokens();
+
+        assert_eq!(scanner.error_handler.had_error, true);
+        assert_eq!(scanner.error_handler.line, 2);
+        assert_eq!(scanner.error_handler.message, "Invalid escape sequence '\\q'.");
+    }
+
+    #[test]
+    fn should_get_invalid_unicode_escape_notification() {
+        let mut error_spy: ErrorSpy = ErrorSpy {
+            line: 0,
+            message: "".to_string(),
+            had_error: false,
+        };
+
+        let mut scanner = Scanner::new(r#""\u{D800}""#, &mut error_spy);
+        scanner.scan_tokens();
+
+        assert_eq!(scanner.error_handler.had_error, true);
+        assert_eq!(scanner.error_handler.message, "Invalid unicode code point '\\u{D800}'.");
+    }
+
+    #[test]
+    fn should_get_interpolation_segments() {
+        let mut error_spy: ErrorSpy = ErrorSpy {
+            line: 0,
+            message: "".to_string(),
+            had_error: false,
+        };
+
+        let mut scanner = Scanner::new(r#""a ${x} b ${ {"k": y}["k"] } c""#, &mut error_spy);
+        let tokens = scanner.scan_tokens();
+
+        let expected_kinds = vec![
+            TokenKind::StringSegment,
+            TokenKind::Identifier,
+            TokenKind::StringSegment,
+            TokenKind::LeftBrace,
+            TokenKind::String,
+            TokenKind::Colon,
+            TokenKind::Identifier,
+            TokenKind::RightBrace,
+            TokenKind::LeftBracket,
+            TokenKind::String,
+            TokenKind::RightBracket,
+            TokenKind::String,
+            TokenKind::EndOfFile,
+        ];
+
+        let kinds: Vec<TokenKind> = tokens.iter().map(|token| token.kind.clone()).collect();
+        assert_eq!(kinds, expected_kinds);
+        assert_eq!(tokens[0].literal, Some(Literal::String("a ".to_string())));
+        assert_eq!(tokens[2].literal, Some(Literal::String(" b ".to_string())));
+        assert_eq!(tokens[11].literal, Some(Literal::String(" c".to_string())));
+        assert_eq!(scanner.error_handler.had_error, false);
+    }
+
+    #[test]
+    fn should_get_unterminated_interpolation_notification() {
+        let mut error_spy: ErrorSpy = ErrorSpy {
+            line: 0,
+            message: "".to_string(),
+            had_error: false,
+        };
+
+        let mut scanner = Scanner::new(r#""a ${x"#, &mut error_spy);
+        scanner.scan_tokens();
+
+        assert_eq!(scanner.error_handler.had_error, true);
+        assert_eq!(scanner.error_handler.message, "Unterminated string interpolation.");
+    }
+
     #[test]
     fn should_get_number_literal() {
         let mut error_spy: ErrorSpy = ErrorSpy {
@@ -400,6 +753,24 @@ mod test {
         assert_eq!(scanner.error_handler.had_error, false);
     }
 
+    #[test]
+    fn should_not_take_range_dots_as_decimal_point() {
+        let mut error_spy: ErrorSpy = ErrorSpy {
+            line: 0,
+            message: "".to_string(),
+            had_error: false,
+        };
+        let mut scanner = Scanner::new("1..=5", &mut error_spy);
+        let tokens = scanner.scan_tokens();
+
+        let kinds: Vec<TokenKind> = tokens.iter().map(|token| token.kind.clone()).collect();
+        assert_eq!(
+            kinds,
+            vec![TokenKind::Number, TokenKind::DotDotEqual, TokenKind::Number, TokenKind::EndOfFile]
+        );
+        assert_eq!(scanner.error_handler.had_error, false);
+    }
+
     #[test]
     fn should_get_identifier() {
         let mut error_spy: ErrorSpy = ErrorSpy {
@@ -422,21 +793,32 @@ mod test {
     fn should_get_reserved_keywords() {
         let expected_tokens: Vec<Keyword> = vec![
             Keyword::new("and", TokenKind::And),
+            Keyword::new("break", TokenKind::Break),
+            Keyword::new("catch", TokenKind::Catch),
             Keyword::new("class", TokenKind::Class),
+            Keyword::new("const", TokenKind::Const),
+            Keyword::new("continue", TokenKind::Continue),
             Keyword::new("else", TokenKind::Else),
+            Keyword::new("enum", TokenKind::Enum),
             Keyword::new("false", TokenKind::False),
+            Keyword::new("finally", TokenKind::Finally),
             Keyword::new("for", TokenKind::For),
             Keyword::new("fun", TokenKind::Fun),
             Keyword::new("if", TokenKind::If),
+            Keyword::new("import", TokenKind::Import),
+            Keyword::new("match", TokenKind::Match),
             Keyword::new("nil", TokenKind::Nil),
             Keyword::new("or", TokenKind::Or),
             Keyword::new("print", TokenKind::Print),
             Keyword::new("return", TokenKind::Return),
             Keyword::new("super", TokenKind::Super),
             Keyword::new("this", TokenKind::This),
+            Keyword::new("throw", TokenKind::Throw),
             Keyword::new("true", TokenKind::True),
+            Keyword::new("try", TokenKind::Try),
             Keyword::new("var", TokenKind::Var),
             Keyword::new("while", TokenKind::While),
+            Keyword::new("yield", TokenKind::Yield),
         ];
 
         for expected_token in expected_tokens {
@@ -7,6 +7,9 @@ pub enum TokenKind {
     RightParen,
     LeftBrace,
     RightBrace,
+    LeftBracket,
+    RightBracket,
+    Colon,
     Comma,
     Dot,
     Minus,
@@ -14,39 +17,66 @@ pub enum TokenKind {
     Semicolon,
     Slash,
     Star,
+    Percent,
+    Question,
+    Pipe,
 
     // One or two character tokens.
     Bang,
     BangEqual,
     Equal,
     EqualEqual,
+    EqualGreater,
     Greater,
     GreaterEqual,
     Less,
     LessEqual,
+    StarStar,
+    TildeSlash,
+    DotDot,
+    DotDotEqual,
+    DotDotDot,
+    PlusEqual,
+    PlusPlus,
+    MinusEqual,
+    MinusMinus,
+    StarEqual,
+    SlashEqual,
 
     // Literals.
     Identifier,
     String,
+    StringSegment,
     Number,
 
     // Keywords.
     And,
+    Break,
+    Catch,
     Class,
+    Const,
+    Continue,
     Else,
+    Enum,
     False,
+    Finally,
     Fun,
     For,
     If,
+    Import,
+    Match,
     Nil,
     Or,
     Print,
     Return,
     Super,
     This,
+    Throw,
     True,
+    Try,
     Var,
     While,
+    Yield,
 
     EndOfFile,
 }
@@ -1,4 +1,6 @@
+import os
 import subprocess
+import tempfile
 
 RLOX_EXECUTABLE = './target/debug/rlox'
 
@@ -7,14 +9,32 @@ class rlox:
     FAILURE = 1
 
     @staticmethod
-    def run(expr: str):
+    def run(expr: str, arguments: tuple[str, ...] = ()):
         FILENAME = '/tmp/test.lox'
 
         with open(FILENAME, "w+") as source:
             source.write(expr)
             source.flush()
 
-            completed_process = subprocess.run(args=[RLOX_EXECUTABLE, FILENAME], capture_output=True ,text=True)
+            completed_process = subprocess.run(args=[RLOX_EXECUTABLE, *arguments, FILENAME], capture_output=True ,text=True)
+            return completed_process.returncode, completed_process.stdout.strip(), completed_process.stderr.strip()
+
+    @staticmethod
+    def run_prompt(lines: list[str], arguments: tuple[str, ...] = ()):
+        completed_process = subprocess.run(args=[RLOX_EXECUTABLE, *arguments], input='\n'.join(lines) + '\n', capture_output=True, text=True)
+        return completed_process.returncode, completed_process.stdout.strip(), completed_process.stderr.strip()
+
+    @staticmethod
+    def run_files(files: dict[str, str], main: str = 'main.lox'):
+        with tempfile.TemporaryDirectory() as directory:
+            for name, content in files.items():
+                path = os.path.join(directory, name)
+                os.makedirs(os.path.dirname(path), exist_ok=True)
+
+                with open(path, "w+") as source:
+                    source.write(content)
+
+            completed_process = subprocess.run(args=[os.path.abspath(RLOX_EXECUTABLE), os.path.join(directory, main)], capture_output=True, text=True)
             return completed_process.returncode, completed_process.stdout.strip(), completed_process.stderr.strip()
 
     @staticmethod
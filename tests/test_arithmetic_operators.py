@@ -0,0 +1,39 @@
+import pytest
+
+from tests.rlox import rlox
+
+@pytest.mark.parametrize(
+    'expression,expected_output',
+    [
+        ('7 % 3', ['1']),
+        ('-7 % 3', ['2']),
+        ('7 % -3', ['-2']),
+        ('5.5 % 2', ['1.5']),
+        ('7 ~/ 2', ['3']),
+        ('-7 ~/ 2', ['-4']),
+        ('2 ** 10', ['1024']),
+        ('2 ** -1', ['0.5']),
+        ('2 ** 3 ** 2', ['512']),
+        ('-2 ** 2', ['-4']),
+        ('1 + 2 * 3 ** 2 % 5', ['4']),
+    ]
+)
+def test_should_evaluate_operator(expression, expected_output):
+    result, stdout, _ = rlox.run(f'print {expression};')
+    assert rlox.succeeded(result, stdout, expected_output)
+
+def test_should_not_confuse_floor_division_with_comment():
+    result, stdout, _ = rlox.run('print 9 ~/ 2; // a comment')
+    assert rlox.succeeded(result, stdout, ['4'])
+
+@pytest.mark.parametrize(
+    'expression',
+    [
+        '"a" % 2',
+        '2 ** nil',
+        'true ~/ 1',
+    ]
+)
+def test_should_not_evaluate_non_number_operands(expression):
+    result, _, stderr = rlox.run(f'print {expression};')
+    assert rlox.failed(result, stderr)
@@ -0,0 +1,100 @@
+import pytest
+
+from tests.rlox import rlox
+
+def test_should_print_class():
+    source = 'class Foo {}      \n' \
+             'print Foo;'
+
+    result, stdout, _ = rlox.run(source)
+    assert rlox.succeeded(result, stdout, ['Foo'])
+
+def test_should_print_instance():
+    source = 'class Foo {}      \n' \
+             'print Foo();'
+
+    result, stdout, _ = rlox.run(source)
+    assert rlox.succeeded(result, stdout, ['Foo instance'])
+
+def test_should_set_and_get_fields():
+    source = 'class Foo {}              \n' \
+             'var foo = Foo();          \n' \
+             'foo.bar = "baz";          \n' \
+             'print foo.bar;'
+
+    result, stdout, _ = rlox.run(source)
+    assert rlox.succeeded(result, stdout, ['"baz"'])
+
+def test_should_call_method():
+    source = 'class Foo {                       \n' \
+             '    bar() {                       \n' \
+             '        return "bar";             \n' \
+             '    }                             \n' \
+             '}                                 \n' \
+             'print Foo().bar();'
+
+    result, stdout, _ = rlox.run(source)
+    assert rlox.succeeded(result, stdout, ['"bar"'])
+
+def test_should_bind_this_to_instance():
+    source = 'class Cake {                                  \n' \
+             '    taste() {                                 \n' \
+             '        print this.flavor;                    \n' \
+             '    }                                         \n' \
+             '}                                             \n' \
+             'var cake = Cake();                            \n' \
+             'cake.flavor = "chocolate";                    \n' \
+             'var taste = cake.taste;                       \n' \
+             'taste();'
+
+    result, stdout, _ = rlox.run(source)
+    assert rlox.succeeded(result, stdout, ['"chocolate"'])
+
+def test_should_run_initializer():
+    source = 'class Point {                     \n' \
+             '    init(x, y) {                  \n' \
+             '        this.x = x;               \n' \
+             '        this.y = y;               \n' \
+             '    }                             \n' \
+             '}                                 \n' \
+             'var point = Point(1, 2);          \n' \
+             'print point.x;                    \n' \
+             'print point.y;'
+
+    result, stdout, _ = rlox.run(source)
+    assert rlox.succeeded(result, stdout, ['1', '2'])
+
+def test_should_return_instance_from_initializer():
+    source = 'class Foo {                       \n' \
+             '    init() {                      \n' \
+             '        return;                   \n' \
+             '    }                             \n' \
+             '}                                 \n' \
+             'var foo = Foo();                  \n' \
+             'print foo.init() == foo;'
+
+    result, stdout, _ = rlox.run(source)
+    assert rlox.succeeded(result, stdout, ['true'])
+
+def test_should_not_call_initializer_with_wrong_arity():
+    source = 'class Foo {                       \n' \
+             '    init(a) {}                    \n' \
+             '}                                 \n' \
+             'Foo();'
+
+    result, _, stderr = rlox.run(source)
+    assert rlox.failed(result, stderr)
+
+def test_should_not_get_undefined_property():
+    source = 'class Foo {}      \n' \
+             'print Foo().bar;'
+
+    result, _, stderr = rlox.run(source)
+    assert rlox.failed(result, stderr)
+
+def test_should_not_set_field_on_non_instance():
+    source = 'var foo = "not an instance";     \n' \
+             'foo.bar = 1;'
+
+    result, _, stderr = rlox.run(source)
+    assert rlox.failed(result, stderr)
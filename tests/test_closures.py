@@ -0,0 +1,50 @@
+import pytest
+
+from tests.rlox import rlox
+
+def test_should_bind_closure_to_declaration_scope():
+    source = 'var a = "global";         \n' \
+             '{                         \n' \
+             '    fun showA() {         \n' \
+             '        print a;          \n' \
+             '    }                     \n' \
+             '                          \n' \
+             '    showA();              \n' \
+             '    var a = "block";      \n' \
+             '    showA();              \n' \
+             '}'
+
+    result, stdout, _ = rlox.run(source)
+    assert rlox.succeeded(result, stdout, ['"global"', '"global"'])
+
+def test_should_assign_captured_variable():
+    source = 'fun makeCounter() {           \n' \
+             '    var i = 0;                \n' \
+             '    fun count() {             \n' \
+             '        i = i + 1;            \n' \
+             '        return i;             \n' \
+             '    }                         \n' \
+             '    return count;             \n' \
+             '}                             \n' \
+             'var first = makeCounter();    \n' \
+             'var second = makeCounter();   \n' \
+             'first();                      \n' \
+             'print first();                \n' \
+             'print second();'
+
+    result, stdout, _ = rlox.run(source)
+    assert rlox.succeeded(result, stdout, ['2', '1'])
+
+def test_should_not_use_this_outside_class():
+    source = 'print this;'
+
+    result, _, stderr = rlox.run(source)
+    assert rlox.failed(result, stderr)
+
+def test_should_not_use_super_without_superclass():
+    source = 'class A {                 \n' \
+             '    f() { super.f(); }    \n' \
+             '}'
+
+    result, _, stderr = rlox.run(source)
+    assert rlox.failed(result, stderr)
@@ -0,0 +1,35 @@
+import pytest
+
+from tests.rlox import rlox
+
+def test_should_ignore_block_comment():
+    source = '/* print "hidden";   \n' \
+             '   print "hidden"; */ \n' \
+             'print "shown";'
+
+    result, stdout, _ = rlox.run(source)
+    assert rlox.succeeded(result, stdout, ['"shown"'])
+
+def test_should_ignore_nested_block_comment():
+    source = '/* outer /* inner */ print "hidden"; */   \n' \
+             'print 1 /* inline */ + 2;'
+
+    result, stdout, _ = rlox.run(source)
+    assert rlox.succeeded(result, stdout, ['3'])
+
+def test_should_keep_line_count_after_block_comment():
+    source = '/*                    \n' \
+             '*/                    \n' \
+             'print undefined;'
+
+    _, _, stderr = rlox.run(source)
+    assert stderr.startswith('[line 3]')
+
+def test_should_report_unterminated_block_comment():
+    source = 'print "ok";           \n' \
+             '/* never closed       \n' \
+             '/* */'
+
+    result, stdout, stderr = rlox.run(source)
+    assert result == rlox.FAILURE
+    assert stderr.startswith('line 2')
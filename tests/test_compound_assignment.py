@@ -0,0 +1,91 @@
+import pytest
+
+from tests.rlox import rlox
+
+@pytest.mark.parametrize(
+    'source,expected_output',
+    [
+        ('var a = 1; a += 2; print a;', ['3']),
+        ('var a = 5; a -= 2; print a;', ['3']),
+        ('var a = 3; a *= 4; print a;', ['12']),
+        ('var a = 9; a /= 2; print a;', ['4.5']),
+        ('var a = 1; print a += 2;', ['3']),
+        ('var a = 1; var b = 2; a += b *= 3; print a; print b;', ['7', '6']),
+        ('var s = "a"; s += "b"; print s;', ['"ab"']),
+    ]
+)
+def test_should_apply_compound_assignment_to_variable(source, expected_output):
+    result, stdout, _ = rlox.run(source)
+    assert rlox.succeeded(result, stdout, expected_output)
+
+@pytest.mark.parametrize(
+    'source,expected_output',
+    [
+        ('var a = 1; print a++; print a;', ['1', '2']),
+        ('var a = 1; print ++a; print a;', ['2', '2']),
+        ('var a = 1; print a--; print a;', ['1', '0']),
+        ('var a = 1; print --a; print a;', ['0', '0']),
+        ('var a = 1; print -a++ - a;', ['-3']),
+    ]
+)
+def test_should_increment_and_decrement_variable(source, expected_output):
+    result, stdout, _ = rlox.run(source)
+    assert rlox.succeeded(result, stdout, expected_output)
+
+def test_should_update_fields_and_indexes():
+    result, stdout, _ = rlox.run('''
+        class Point {}
+        var p = Point();
+        p.x = 5;
+        p.x -= 1;
+        print p.x++;
+        print p.x;
+
+        var list = [1, 2, 3];
+        list[1] *= 10;
+        print --list[2];
+        print list;
+
+        var map = {"k": 8};
+        map["k"] /= 2;
+        print map["k"];
+    ''')
+    assert rlox.succeeded(result, stdout, ['4', '5', '2', '[1, 20, 2]', '4'])
+
+def test_should_evaluate_target_once():
+    result, stdout, _ = rlox.run('''
+        var list = [1, 2, 3];
+        var i = 0;
+        fun items() { print "items"; return list; }
+        items()[i++] += 10;
+        print list;
+        print i;
+    ''')
+    assert rlox.succeeded(result, stdout, ['"items"', '[11, 2, 3]', '1'])
+
+@pytest.mark.parametrize(
+    'source',
+    [
+        '1 += 2;',
+        'var a = 1; (a) -= 1;',
+        '++1;',
+        'var a = 1; a++++;',
+        'fun f() {} f()--;',
+    ]
+)
+def test_should_not_assign_to_invalid_target(source):
+    result, _, stderr = rlox.run(source)
+    assert rlox.failed(result, stderr)
+    assert 'Invalid assignment target.' in stderr
+
+@pytest.mark.parametrize(
+    'source',
+    [
+        'var s = "x"; s++;',
+        'var a = nil; a -= 1;',
+        'var a; a *= 2;',
+    ]
+)
+def test_should_not_update_non_number_values(source):
+    result, _, stderr = rlox.run(source)
+    assert rlox.failed(result, stderr)
@@ -0,0 +1,74 @@
+import pytest
+
+from tests.rlox import rlox
+
+@pytest.mark.parametrize(
+    'expression,expected_output',
+    [
+        ('true ? 1 : 2', ['1']),
+        ('false ? 1 : 2', ['2']),
+        ('nil ? 1 : 2', ['2']),
+        ('0 ? 1 : 2', ['1']),
+        ('false ? 1 : true ? 2 : 3', ['2']),
+        ('true ? false ? 1 : 2 : 3', ['2']),
+        ('1 < 2 and 2 < 3 ? "yes" : "no"', ['"yes"']),
+        ('(true ? 1 : 2) + 10', ['11']),
+    ]
+)
+def test_should_evaluate_conditional(expression, expected_output):
+    result, stdout, _ = rlox.run(f'print {expression};')
+    assert rlox.succeeded(result, stdout, expected_output)
+
+def test_should_only_evaluate_taken_branch():
+    result, stdout, _ = rlox.run('''
+        fun side(value) { print value; return value; }
+        print true ? side("then") : side("else");
+        print false ? side("then") : side("else");
+    ''')
+    assert rlox.succeeded(result, stdout, ['"then"', '"then"', '"else"', '"else"'])
+
+def test_should_assign_result_of_conditional():
+    result, stdout, _ = rlox.run('var a; a = false ? 1 : 2; print a;')
+    assert rlox.succeeded(result, stdout, ['2'])
+
+def test_should_not_parse_conditional_without_else_branch():
+    result, _, stderr = rlox.run('print true ? 1;')
+    assert rlox.failed(result, stderr)
+
+def test_should_evaluate_comma_to_right_operand():
+    result, stdout, _ = rlox.run('''
+        var a = 0;
+        var b = 0;
+        print (a = 1, b = 2, a + b);
+        print a;
+        print b;
+    ''')
+    assert rlox.succeeded(result, stdout, ['3', '1', '2'])
+
+def test_should_use_comma_in_for_increment():
+    result, stdout, _ = rlox.run('''
+        var j = 10;
+        for (var i = 0; i < 3; i = i + 1, j = j - 1) print i + j;
+    ''')
+    assert rlox.succeeded(result, stdout, ['10', '10', '10'])
+
+def test_should_keep_arguments_and_elements_separate():
+    result, stdout, _ = rlox.run('''
+        fun add(a, b) { return a + b; }
+        print add(1, 2);
+        print add((1, 2), 3);
+        print [1, 2, 3];
+        print {"a": 1, "b": 2}["b"];
+    ''')
+    assert rlox.succeeded(result, stdout, ['3', '5', '[1, 2, 3]', '2'])
+
+@pytest.mark.parametrize('source', ['var a = 1, b = 2;', 'const a = 1, b = 2;'])
+def test_should_not_parse_comma_in_initializer(source):
+    result, _, stderr = rlox.run(source)
+    assert rlox.failed(result, stderr)
+    assert "Error at ','" in stderr
+    assert 'Undefined variable' not in stderr
+
+def test_should_allow_parenthesized_comma_in_initializer():
+    result, stdout, _ = rlox.run('var a = (1, 2); print a;')
+    assert rlox.succeeded(result, stdout, ['2'])
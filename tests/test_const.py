@@ -0,0 +1,98 @@
+import pytest
+
+from tests.rlox import rlox
+
+def test_should_read_constant():
+    result, stdout, _ = rlox.run('''
+        const limit = 10;
+        fun show() { const doubled = limit * 2; print doubled; }
+        print limit;
+        show();
+    ''')
+    assert rlox.succeeded(result, stdout, ['10', '20'])
+
+@pytest.mark.parametrize(
+    'statement',
+    [
+        'limit = 2;',
+        'limit += 2;',
+        'limit++;',
+        '--limit;',
+        'fun f() { limit = 2; }',
+        '{ const other = 1; { other = 2; } }',
+    ]
+)
+def test_should_report_assignment_to_constant_statically(statement):
+    result, stdout, stderr = rlox.run('const limit = 1; print "ran";' + statement)
+    assert rlox.failed(result, stderr)
+    assert stdout == ''
+    assert "Can't assign to constant" in stderr
+
+def test_should_report_assignment_to_constant_at_runtime():
+    # The function is resolved before the constant is declared, so only the assignment itself can catch it
+    result, stdout, stderr = rlox.run('''
+        fun reset() { limit = 0; }
+        const limit = 1;
+        reset();
+    ''')
+    assert rlox.failed(result, stderr)
+    assert "Can't assign to constant 'limit'." in stderr
+    assert '[line 2]' in stderr
+
+def test_should_report_redefinition_of_constant():
+    result, _, stderr = rlox.run('const limit = 1; var limit = 2;')
+    assert rlox.failed(result, stderr)
+    assert "Can't redefine constant 'limit'." in stderr
+
+def test_should_allow_shadowing_constant_in_inner_scope():
+    result, stdout, _ = rlox.run('''
+        const limit = 1;
+        {
+            var limit = 2;
+            limit = 3;
+            print limit;
+        }
+        print limit;
+    ''')
+    assert rlox.succeeded(result, stdout, ['3', '1'])
+
+def test_should_not_freeze_constant_value():
+    result, stdout, _ = rlox.run('''
+        const items = [1];
+        items[0] = 2;
+        print items[0];
+    ''')
+    assert rlox.succeeded(result, stdout, ['2'])
+
+@pytest.mark.parametrize('native', ['clock', 'len', 'push'])
+def test_should_protect_native_functions(native):
+    result, _, stderr = rlox.run(f'{native} = nil;')
+    assert rlox.failed(result, stderr)
+    assert f"Can't assign to constant '{native}'." in stderr
+
+@pytest.mark.parametrize(
+    'declaration,use',
+    [
+        ('var values = [];', 'print values;'),
+        ('fun sort(list) { return "mine"; }', 'print sort([2, 1]);'),
+        ('class keys {}', 'print keys;'),
+        ('const len = 1;', 'print len;'),
+    ],
+)
+def test_should_let_declarations_replace_native_functions(declaration, use):
+    result, stdout, _ = rlox.run(f'{declaration} {use}')
+    assert result == rlox.SUCCESS
+    assert stdout != ''
+
+def test_should_make_replaced_native_behave_like_its_declaration():
+    result, stdout, _ = rlox.run('var values = []; values = [1]; print values;')
+    assert rlox.succeeded(result, stdout, ['[1]'])
+
+    result, _, stderr = rlox.run('const len = 1; len = 2;')
+    assert rlox.failed(result, stderr)
+    assert "Can't assign to constant 'len'." in stderr
+
+def test_should_require_initializer():
+    result, _, stderr = rlox.run('const limit;')
+    assert rlox.failed(result, stderr)
+    assert "Expect '=' after constant name." in stderr
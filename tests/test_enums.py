@@ -0,0 +1,115 @@
+import pytest
+
+from tests.rlox import rlox
+
+SHAPE = '''
+    enum Shape { Circle(r), Rect(w, h), Empty }
+'''
+
+@pytest.mark.parametrize(
+    'expression,expected_output',
+    [
+        ('Shape.Circle(2)', ['Shape.Circle(2)']),
+        ('Shape.Rect(1, "a")', ['Shape.Rect(1, "a")']),
+        ('Shape.Empty', ['Shape.Empty']),
+        ('Shape.Rect', ['<variant Shape.Rect>']),
+        ('Shape', ['<enum Shape>']),
+        ('Shape.Rect(3, 4).h', ['4']),
+        ('Shape.Rect(h: 2, w: 1)', ['Shape.Rect(1, 2)']),
+        ('[Shape.Empty, Shape.Circle(nil)]', ['[Shape.Empty, Shape.Circle(nil)]']),
+    ]
+)
+def test_should_construct_variant(expression, expected_output):
+    result, stdout, _ = rlox.run(SHAPE + f'print {expression};')
+    assert rlox.succeeded(result, stdout, expected_output)
+
+@pytest.mark.parametrize(
+    'expression,expected_output',
+    [
+        ('Shape.Circle(2) == Shape.Circle(2)', ['true']),
+        ('Shape.Circle(2) == Shape.Circle(3)', ['false']),
+        ('Shape.Empty == Shape.Empty', ['true']),
+        ('Shape.Rect(1, 2) == Shape.Rect(2, 1)', ['false']),
+        ('Shape.Circle(1) != Shape.Empty', ['true']),
+        ('Shape.Circle([1]) == Shape.Circle([1])', ['true']),
+    ]
+)
+def test_should_compare_values_by_variant_and_fields(expression, expected_output):
+    result, stdout, _ = rlox.run(SHAPE + f'print {expression};')
+    assert rlox.succeeded(result, stdout, expected_output)
+
+def test_should_tell_apart_enums_with_same_variant_names():
+    result, stdout, _ = rlox.run('''
+        enum A { Unit }
+        enum B { Unit }
+        print A.Unit == B.Unit;
+    ''')
+    assert rlox.succeeded(result, stdout, ['false'])
+
+def test_should_destructure_variant_in_match():
+    result, stdout, _ = rlox.run(SHAPE + '''
+        fun area(shape) {
+            match (shape) {
+                Shape.Circle(r) => return 3 * r * r;
+                Shape.Rect(w, h) if w == h => { print "square"; return w * w; }
+                Shape.Rect(w, _) => return w;
+                Shape.Empty => return 0;
+            }
+        }
+        print area(Shape.Circle(2));
+        print area(Shape.Rect(3, 3));
+        print area(Shape.Rect(5, 2));
+        print area(Shape.Empty);
+    ''')
+    assert rlox.succeeded(result, stdout, ['12', '"square"', '9', '5', '0'])
+
+def test_should_scope_bindings_to_arm():
+    result, stdout, _ = rlox.run(SHAPE + '''
+        var r = "outer";
+        match (Shape.Circle(1)) {
+            Shape.Circle(r) => print r;
+        }
+        print r;
+    ''')
+    assert rlox.succeeded(result, stdout, ['1', '"outer"'])
+
+def test_should_match_variant_without_binding_fields():
+    result, stdout, _ = rlox.run(SHAPE + '''
+        match (Shape.Rect(1, 2)) {
+            Shape.Circle | Shape.Rect => print "has fields";
+            _ => print "empty";
+        }
+    ''')
+    assert rlox.succeeded(result, stdout, ['"has fields"'])
+
+@pytest.mark.parametrize(
+    'source,message',
+    [
+        ('Shape.Rect(1);', 'Expected 2 arguments, but got 1.'),
+        ('Shape.Circle(1, 2);', 'Expected 1 arguments, but got 2.'),
+        ('Shape.Circle(radius: 1);', "Unknown argument 'radius' for Shape.Circle."),
+        ('Shape.Empty();', 'Can only call functions and classes'),
+        ('Shape.Square;', "Enum 'Shape' has no variant 'Square'."),
+        ('Shape.Circle(1).d;', "Undefined property 'd'."),
+        ('Shape.Circle(1).r = 2;', 'Only instances have fields.'),
+        ('match (Shape.Rect(1, 2)) { Shape.Rect(w) => print w; }', 'Expected 2 fields in pattern for Rect, but got 1.'),
+    ]
+)
+def test_should_report_runtime_error(source, message):
+    result, _, stderr = rlox.run(SHAPE + source)
+    assert rlox.failed(result, stderr)
+    assert message in stderr
+
+@pytest.mark.parametrize(
+    'source,message',
+    [
+        ('enum E { A, A }', 'Already a variant with this name in this enum.'),
+        ('enum E { A(x, x) }', 'Already a field with this name in this variant.'),
+        ('enum E { A B }', "Expect '}' after enum body."),
+        ('match (1) { E.A(x) | E.B(x) => print x; }', "Can't bind fields in an arm with several patterns."),
+    ]
+)
+def test_should_report_parse_error(source, message):
+    result, _, stderr = rlox.run(source)
+    assert rlox.failed(result, stderr)
+    assert message in stderr
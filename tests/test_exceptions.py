@@ -0,0 +1,141 @@
+import pytest
+
+from tests.rlox import rlox
+
+@pytest.mark.parametrize(
+    'thrown,expected_output',
+    [
+        ('"boom"', ['"boom"']),
+        ('42', ['42']),
+        ('[1, 2]', ['[1, 2]']),
+        ('nil', ['nil']),
+    ]
+)
+def test_should_catch_thrown_value(thrown, expected_output):
+    result, stdout, _ = rlox.run(f'try {{ throw {thrown}; }} catch (e) {{ print e; }}')
+    assert rlox.succeeded(result, stdout, expected_output)
+
+def test_should_skip_rest_of_try_block_after_throw():
+    result, stdout, _ = rlox.run('''
+        try {
+            print "before";
+            throw "boom";
+            print "after";
+        } catch (e) {
+            print "caught";
+        }
+        print "done";
+    ''')
+    assert rlox.succeeded(result, stdout, ['"before"', '"caught"', '"done"'])
+
+def test_should_propagate_throw_out_of_functions():
+    result, stdout, _ = rlox.run('''
+        fun fail() { throw "from function"; }
+        fun outer() { fail(); print "unreachable"; }
+        try { outer(); } catch (e) { print e; }
+    ''')
+    assert rlox.succeeded(result, stdout, ['"from function"'])
+
+@pytest.mark.parametrize(
+    'statement,expected_message',
+    [
+        ('print undefined;', '"Undefined variable \'undefined\'."'),
+        ('1 + nil;', '"Invalid operands 1 and nil to operator \'+\'"'),
+        ('fun f(a) {} f();', '"Expected 1 arguments, but got 0."'),
+        ('"text"();', '"Can only call functions and classes"'),
+    ]
+)
+def test_should_catch_runtime_errors_as_values(statement, expected_message):
+    result, stdout, _ = rlox.run(f'''
+        try {{
+            {statement}
+        }} catch (e) {{
+            print e.message;
+            print e.line;
+        }}
+    ''')
+    assert rlox.succeeded(result, stdout, [expected_message, '3'])
+
+def test_should_run_finally_after_try_and_catch():
+    result, stdout, _ = rlox.run('''
+        try { print "try"; } finally { print "finally"; }
+        try { throw 1; } catch (e) { print "catch"; } finally { print "finally"; }
+    ''')
+    assert rlox.succeeded(result, stdout, ['"try"', '"finally"', '"catch"', '"finally"'])
+
+def test_should_run_finally_when_returning_from_try():
+    result, stdout, _ = rlox.run('''
+        fun f() {
+            try {
+                return "value";
+            } finally {
+                print "cleanup";
+            }
+        }
+        print f();
+    ''')
+    assert rlox.succeeded(result, stdout, ['"cleanup"', '"value"'])
+
+def test_should_let_finally_override_pending_result():
+    result, stdout, _ = rlox.run('''
+        fun f() {
+            try { return "try"; } finally { return "finally"; }
+        }
+        print f();
+    ''')
+    assert rlox.succeeded(result, stdout, ['"finally"'])
+
+def test_should_run_finally_on_loop_control():
+    result, stdout, _ = rlox.run('''
+        for (var i = 0; i < 3; i = i + 1) {
+            try {
+                if (i == 1) continue;
+                if (i == 2) break;
+                print i;
+            } finally {
+                print "finally";
+            }
+        }
+    ''')
+    assert rlox.succeeded(result, stdout, ['0', '"finally"', '"finally"', '"finally"'])
+
+def test_should_rethrow_from_nested_try():
+    result, stdout, _ = rlox.run('''
+        try {
+            try {
+                throw "inner";
+            } catch (e) {
+                throw "rethrown " + e;
+            } finally {
+                print "inner finally";
+            }
+        } catch (e) {
+            print e;
+        }
+    ''')
+    assert rlox.succeeded(result, stdout, ['"inner finally"', '"rethrown inner"'])
+
+def test_should_scope_exception_variable_to_catch_body():
+    result, stdout, _ = rlox.run('''
+        var e = "outer";
+        try { throw "inner"; } catch (e) { print e; }
+        print e;
+    ''')
+    assert rlox.succeeded(result, stdout, ['"inner"', '"outer"'])
+
+def test_should_report_uncaught_exception():
+    result, _, stderr = rlox.run('throw "boom";')
+    assert rlox.failed(result, stderr)
+    assert 'Uncaught exception "boom"' in stderr
+
+@pytest.mark.parametrize(
+    'source',
+    [
+        'try { print 1; }',
+        'try { } catch { }',
+        'try { } catch (1) { }',
+    ]
+)
+def test_should_not_parse_malformed_exception_statements(source):
+    result, _, stderr = rlox.run(source)
+    assert rlox.failed(result, stderr)
@@ -0,0 +1,121 @@
+import pytest
+
+from tests.rlox import rlox
+
+@pytest.mark.parametrize(
+    'iterable,expected_output',
+    [
+        ('[1, "two", nil]', ['1', '"two"', 'nil']),
+        ('"abc"', ['"a"', '"b"', '"c"']),
+        ('{"x": 1, "y": 2}', ['"x"', '"y"']),
+        ('0..3', ['0', '1', '2']),
+        ('0..=3', ['0', '1', '2', '3']),
+        ('0..10 step 4', ['0', '4', '8']),
+        ('3..0 step -1', ['3', '2', '1']),
+        ('3..=0 step -1.5', ['3', '1.5', '0']),
+        ('0..1 step 0.25', ['0', '0.25', '0.5', '0.75']),
+    ]
+)
+def test_should_iterate_over_value(iterable, expected_output):
+    result, stdout, _ = rlox.run(f'for (x in {iterable}) print x; print "done";')
+    assert rlox.succeeded(result, stdout, expected_output + ['"done"'])
+
+@pytest.mark.parametrize('iterable', ['[]', '""', '{}', '0..0', '5..0', '0..=-1'])
+def test_should_skip_body_for_empty_iterable(iterable):
+    result, stdout, _ = rlox.run(f'for (x in {iterable}) print x; print "done";')
+    assert rlox.succeeded(result, stdout, ['"done"'])
+
+def test_should_bind_fresh_variable_per_iteration():
+    result, stdout, _ = rlox.run('''
+        var closures = [];
+        for (i in 0..3) {
+            push(closures, fun () { return i; });
+        }
+        for (closure in closures) print closure();
+    ''')
+    assert rlox.succeeded(result, stdout, ['0', '1', '2'])
+
+def test_should_not_leak_loop_variable():
+    result, _, stderr = rlox.run('for (i in 0..3) {} print i;')
+    assert rlox.failed(result, stderr)
+    assert "Undefined variable 'i'." in stderr
+
+def test_should_support_break_and_continue():
+    result, stdout, _ = rlox.run('''
+        for (i in 0..10) {
+            if (i == 1) continue;
+            if (i == 4) break;
+            print i;
+        }
+    ''')
+    assert rlox.succeeded(result, stdout, ['0', '2', '3'])
+
+def test_should_visit_elements_pushed_during_loop():
+    result, stdout, _ = rlox.run('''
+        var items = [1];
+        for (x in items) {
+            print x;
+            if (x < 3) push(items, x + 1);
+        }
+    ''')
+    assert rlox.succeeded(result, stdout, ['1', '2', '3'])
+
+def test_should_iterate_over_object_with_iterator_protocol():
+    result, stdout, _ = rlox.run('''
+        class Countdown {
+            init(from) { this.current = from; }
+            hasNext() { return this.current > 0; }
+            next() { this.current -= 1; return this.current + 1; }
+        }
+        for (n in Countdown(3)) print n;
+    ''')
+    assert rlox.succeeded(result, stdout, ['3', '2', '1'])
+
+def test_should_iterate_over_value_returned_by_iterator_method():
+    result, stdout, _ = rlox.run('''
+        class Team {
+            init() { this.members = ["ann", "bob"]; }
+            iterator() { return this.members; }
+        }
+        for (member in Team()) print member;
+    ''')
+    assert rlox.succeeded(result, stdout, ['"ann"', '"bob"'])
+
+@pytest.mark.parametrize(
+    'expression,expected_output',
+    [
+        ('0..5', ['0..5']),
+        ('1..=n', ['1..=3']),
+        ('0..n - 1 step 2', ['0..2 step 2']),
+        ('0..3 == 0..3', ['true']),
+        ('0..3 == 0..=3', ['false']),
+    ]
+)
+def test_should_evaluate_range_expression(expression, expected_output):
+    result, stdout, _ = rlox.run(f'var n = 3; print {expression};')
+    assert rlox.succeeded(result, stdout, expected_output)
+
+def test_should_keep_in_and_step_usable_as_names():
+    result, stdout, _ = rlox.run('''
+        var in = [1, 2];
+        var step = 2;
+        for (x in in) print x;
+        for (x in 0..4 step step) print x;
+    ''')
+    assert rlox.succeeded(result, stdout, ['1', '2', '0', '2'])
+
+@pytest.mark.parametrize(
+    'source,message',
+    [
+        ('for (x in 5) print x;', "Can't iterate over 5."),
+        ('for (x in nil) print x;', "Can't iterate over nil."),
+        ('for (x in 0..3 step 0) print x;', "Range step can't be zero."),
+        ('print 0.."a";', 'Range bounds and step must be numbers.'),
+        ('class C {} for (x in C()) print x;', "Undefined property 'hasNext'."),
+        ('class C { init() { this.hasNext = true; } } for (x in C()) print x;', "Iterator property 'hasNext' must be a method."),
+    ]
+)
+def test_should_report_runtime_error(source, message):
+    result, _, stderr = rlox.run(source)
+    assert rlox.failed(result, stderr)
+    assert message in stderr
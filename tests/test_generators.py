@@ -0,0 +1,271 @@
+import pytest
+
+from tests.rlox import rlox
+
+COUNT = '''
+    fun count(n) {
+        var i = 0;
+        while (i < n) {
+            yield i;
+            i += 1;
+        }
+    }
+'''
+
+def test_should_resume_where_it_left_off():
+    result, stdout, _ = rlox.run(COUNT + '''
+        var counter = count(2);
+        print counter;
+        print counter.next();
+        print counter.next();
+        print counter.next();
+        print counter.next();
+    ''')
+    assert rlox.succeeded(result, stdout, ['<generator count>', '0', '1', 'nil', 'nil'])
+
+def test_should_not_run_body_before_first_next():
+    result, stdout, _ = rlox.run('''
+        fun noisy() {
+            print "started";
+            yield 1;
+            print "resumed";
+        }
+        var g = noisy();
+        print "created";
+        print g.next();
+        print "between";
+        print g.next();
+    ''')
+    assert rlox.succeeded(result, stdout, ['"created"', '"started"', '1', '"between"', '"resumed"', 'nil'])
+
+def test_should_look_ahead_with_has_next():
+    result, stdout, _ = rlox.run(COUNT + '''
+        var counter = count(1);
+        print counter.hasNext();
+        print counter.hasNext();
+        print counter.next();
+        print counter.hasNext();
+    ''')
+    assert rlox.succeeded(result, stdout, ['true', 'true', '0', 'false'])
+
+def test_should_iterate_in_for_in_loop():
+    result, stdout, _ = rlox.run(COUNT + 'for (n in count(3)) print n;')
+    assert rlox.succeeded(result, stdout, ['0', '1', '2'])
+
+def test_should_stop_infinite_generator_with_break():
+    result, stdout, _ = rlox.run('''
+        fun naturals() {
+            for (var n = 0; ; n = n + 1) yield n;
+        }
+        for (n in naturals()) {
+            if (n == 1) continue;
+            if (n > 3) break;
+            print n;
+        }
+    ''')
+    assert rlox.succeeded(result, stdout, ['0', '2', '3'])
+
+def test_should_suspend_inside_nested_loops_and_branches():
+    result, stdout, _ = rlox.run('''
+        fun pairs() {
+            for (a in 0..3) {
+                var b = 0;
+                while (b < a) {
+                    if (a == 2 and b == 0) {
+                        b += 1;
+                        continue;
+                    }
+                    yield [a, b];
+                    b += 1;
+                }
+            }
+            yield "done";
+        }
+        for (pair in pairs()) print pair;
+    ''')
+    assert rlox.succeeded(result, stdout, ['[1, 0]', '[2, 1]', '"done"'])
+
+def test_should_yield_from_match_arms():
+    result, stdout, _ = rlox.run('''
+        enum Tree { Leaf, Node(left, value, right) }
+        fun walk(tree) {
+            match (tree) {
+                Tree.Node(left, value, right) => {
+                    for (x in walk(left)) yield x;
+                    yield value;
+                    for (x in walk(right)) yield x;
+                }
+                Tree.Leaf => {}
+            }
+        }
+        var tree = Tree.Node(Tree.Node(Tree.Leaf, 1, Tree.Leaf), 2, Tree.Node(Tree.Leaf, 3, Tree.Leaf));
+        for (x in walk(tree)) print x;
+    ''')
+    assert rlox.succeeded(result, stdout, ['1', '2', '3'])
+
+def test_should_finish_on_return():
+    result, stdout, _ = rlox.run('''
+        fun early() {
+            yield 1;
+            return;
+            yield 2;
+        }
+        for (x in early()) print x;
+        print "after";
+    ''')
+    assert rlox.succeeded(result, stdout, ['1', '"after"'])
+
+def test_should_keep_generators_independent():
+    result, stdout, _ = rlox.run(COUNT + '''
+        var a = count(3);
+        var b = count(3);
+        print a.next();
+        print a.next();
+        print b.next();
+    ''')
+    assert rlox.succeeded(result, stdout, ['0', '1', '0'])
+
+def test_should_support_generator_methods_and_lambdas():
+    result, stdout, _ = rlox.run('''
+        class Bag {
+            init() { this.items = ["x", "y"]; }
+            iterator() {
+                for (item in this.items) yield item;
+            }
+        }
+        for (item in Bag()) print item;
+        var twice = fun (value) { yield value; yield value; };
+        for (v in twice(7)) print v;
+    ''')
+    assert rlox.succeeded(result, stdout, ['"x"', '"y"', '7', '7'])
+
+def test_should_capture_fresh_binding_per_iteration():
+    result, stdout, _ = rlox.run('''
+        fun makers() {
+            for (i in 0..3) yield fun () { return i; };
+        }
+        for (make in makers()) print make();
+    ''')
+    assert rlox.succeeded(result, stdout, ['0', '1', '2'])
+
+def test_should_stay_finished_after_error():
+    result, stdout, stderr = rlox.run('''
+        fun failing() {
+            yield 1;
+            throw "boom";
+        }
+        var g = failing();
+        print g.next();
+        try {
+            g.next();
+        } catch (e) {
+            print e;
+        }
+        print g.next();
+    ''')
+    assert rlox.succeeded(result, stdout, ['1', '"boom"', 'nil'])
+
+def test_should_yield_from_try_catch_and_finally():
+    result, stdout, _ = rlox.run('''
+        fun attempt() {
+            try {
+                yield 1;
+                throw "boom";
+                yield "unreachable";
+            } catch (error) {
+                yield "caught " + error;
+            } finally {
+                yield "finally";
+            }
+            yield 2;
+        }
+        for (x in attempt()) print x;
+    ''')
+    assert rlox.succeeded(result, stdout, ['1', '"caught boom"', '"finally"', '2'])
+
+def test_should_run_finally_when_leaving_try_in_generator():
+    result, stdout, _ = rlox.run('''
+        fun numbers() {
+            for (i in 0..5) {
+                try {
+                    if (i == 1) continue;
+                    if (i == 3) break;
+                    yield i;
+                } finally {
+                    print "cleanup";
+                }
+            }
+            try {
+                yield "last";
+                return;
+            } finally {
+                print "closing";
+            }
+        }
+        for (x in numbers()) print x;
+    ''')
+    assert rlox.succeeded(result, stdout, ['0', '"cleanup"', '"cleanup"', '2', '"cleanup"', '"cleanup"', '"last"', '"closing"'])
+
+def test_should_catch_runtime_error_in_outer_try_of_generator():
+    result, stdout, _ = rlox.run('''
+        fun nested() {
+            try {
+                try {
+                    yield 1;
+                    missing;
+                } finally {
+                    yield "inner finally";
+                }
+            } catch (error) {
+                yield error.message;
+            }
+        }
+        for (x in nested()) print x;
+    ''')
+    assert rlox.succeeded(result, stdout, ['1', '"inner finally"', '"Undefined variable \'missing\'."'])
+
+def test_should_pass_uncaught_error_out_of_generator_after_finally():
+    result, stdout, _ = rlox.run('''
+        fun failing() {
+            try {
+                yield 1;
+                throw "boom";
+            } finally {
+                print "finally";
+            }
+        }
+        var g = failing();
+        print g.next();
+        try {
+            g.next();
+        } catch (e) {
+            print e;
+        }
+        print g.next();
+    ''')
+    assert rlox.succeeded(result, stdout, ['1', '"finally"', '"boom"', 'nil'])
+
+@pytest.mark.parametrize(
+    'source,message',
+    [
+        ('fun f() { yield me.next(); } var me = f(); me.next();', 'Generator is already running.'),
+        (COUNT + 'count(1).value;', "Undefined property 'value'."),
+    ]
+)
+def test_should_report_runtime_error(source, message):
+    result, _, stderr = rlox.run(source)
+    assert rlox.failed(result, stderr)
+    assert message in stderr
+
+@pytest.mark.parametrize(
+    'source,message',
+    [
+        ('yield 1;', "Can't yield from top-level code."),
+        ('class C { init() { yield 1; } }', "Can't yield from an initializer."),
+        ('fun f() { yield 1; return 2; }', "Can't return a value from a generator."),
+    ]
+)
+def test_should_report_resolve_error(source, message):
+    result, _, stderr = rlox.run(source)
+    assert rlox.failed(result, stderr)
+    assert message in stderr
@@ -0,0 +1,159 @@
+import pytest
+
+from tests.rlox import rlox
+
+MATH_MODULE = '''
+    var pi = 3;
+    fun area(r) { return pi * r * r; }
+    fun setPi(value) { pi = value; }
+'''
+
+def test_should_bind_module_to_file_name():
+    result, stdout, _ = rlox.run_files({
+        'main.lox': 'import "math.lox"; print math.area(2); print math;',
+        'math.lox': MATH_MODULE,
+    })
+    assert rlox.succeeded(result, stdout, ['12', '<module math>'])
+
+def test_should_bind_module_to_alias():
+    result, stdout, _ = rlox.run_files({
+        'main.lox': 'import "math.lox" as m; print m.pi;',
+        'math.lox': MATH_MODULE,
+    })
+    assert rlox.succeeded(result, stdout, ['3'])
+
+def test_should_resolve_paths_relative_to_importing_file():
+    result, stdout, _ = rlox.run_files({
+        'main.lox': 'import "lib/shapes.lox"; print shapes.square(3);',
+        'lib/shapes.lox': 'import "../math.lox"; fun square(side) { return side * side + math.pi; }',
+        'math.lox': MATH_MODULE,
+    })
+    assert rlox.succeeded(result, stdout, ['12'])
+
+def test_should_run_each_module_once():
+    result, stdout, _ = rlox.run_files({
+        'main.lox': '''
+            import "counter.lox";
+            import "counter.lox" as again;
+            import "other.lox";
+            print counter == again;
+        ''',
+        'counter.lox': 'print "loading counter"; var count = 0;',
+        'other.lox': 'import "counter.lox"; print "loading other";',
+    })
+    assert rlox.succeeded(result, stdout, ['"loading counter"', '"loading other"', 'true'])
+    assert stdout.count('loading counter') == 1
+
+def test_should_keep_module_globals_separate():
+    result, stdout, _ = rlox.run_files({
+        'main.lox': '''
+            import "math.lox";
+            var pi = 100;
+            print math.area(1);
+            math.setPi(4);
+            print math.pi;
+            print pi;
+        ''',
+        'math.lox': MATH_MODULE,
+    })
+    assert rlox.succeeded(result, stdout, ['3', '4', '100'])
+
+def test_should_not_find_missing_member():
+    result, _, stderr = rlox.run_files({
+        'main.lox': 'import "math.lox"; print math.tau;',
+        'math.lox': MATH_MODULE,
+    })
+    assert rlox.failed(result, stderr)
+    assert "Module 'math' has no member 'tau'." in stderr
+
+def test_should_report_missing_module():
+    result, _, stderr = rlox.run_files({
+        'main.lox': 'import "missing.lox";',
+    })
+    assert rlox.failed(result, stderr)
+    assert "Can't find module 'missing.lox'." in stderr
+
+def test_should_report_import_cycle_with_chain():
+    result, _, stderr = rlox.run_files({
+        'main.lox': 'import "a.lox";',
+        'a.lox': 'import "b.lox";',
+        'b.lox': 'import "a.lox";',
+    })
+    assert rlox.failed(result, stderr)
+    assert 'Import cycle detected:' in stderr
+    chain = stderr.split('Import cycle detected:')[1]
+    assert chain.index('a.lox') < chain.index('b.lox') < chain.rindex('a.lox')
+
+def test_should_report_errors_in_module():
+    result, _, stderr = rlox.run_files({
+        'main.lox': 'import "broken.lox";',
+        'broken.lox': 'var = 1;',
+    })
+    assert rlox.failed(result, stderr)
+    assert "Module 'broken.lox' has errors." in stderr
+
+def test_should_name_module_file_in_its_errors():
+    result, _, stderr = rlox.run_files({
+        'main.lox': 'import "broken.lox";',
+        'broken.lox': 'var = 1;',
+    })
+    assert rlox.failed(result, stderr)
+    assert "broken.lox: line 1 Error at '=': " in stderr
+
+def test_should_stop_importing_script_on_runtime_error_in_module():
+    result, stdout, stderr = rlox.run_files({
+        'main.lox': 'import "failing.lox"; print "end";',
+        'failing.lox': 'print "loading"; nil();',
+    })
+    assert result != rlox.SUCCESS
+    assert stdout == '"loading"'
+    assert 'Can only call functions and classes' in stderr
+
+def test_should_not_rerun_module_that_failed():
+    result, stdout, _ = rlox.run_files({
+        'main.lox': '''
+            try {
+                import "failing.lox";
+            } catch (error) {
+                print "first";
+            }
+            try {
+                import "failing.lox";
+            } catch (error) {
+                print error.message;
+            }
+        ''',
+        'failing.lox': 'print "loading"; throw "boom";',
+    })
+    assert rlox.succeeded(result, stdout, ['"loading"', '"first"', '"Module \'failing.lox\' failed to load."'])
+    assert stdout.count('loading') == 1
+
+@pytest.mark.parametrize('member', ['clock', 'len', 'push'])
+def test_should_not_export_natives(member):
+    result, _, stderr = rlox.run_files({
+        'main.lox': f'import "math.lox"; print math.{member};',
+        'math.lox': MATH_MODULE,
+    })
+    assert rlox.failed(result, stderr)
+    assert f"Module 'math' has no member '{member}'." in stderr
+
+def test_should_export_declaration_replacing_native():
+    result, stdout, _ = rlox.run_files({
+        'main.lox': 'import "sizes.lox"; print sizes.len([1, 2]);',
+        'sizes.lox': 'fun len(list) { return "custom"; }',
+    })
+    assert rlox.succeeded(result, stdout, ['"custom"'])
+
+@pytest.mark.parametrize(
+    'source',
+    [
+        'import "my-module.lox";',
+        'import "class.lox";',
+        'import math.lox;',
+        'import "math.lox" as;',
+        'import "math.lox"',
+    ]
+)
+def test_should_not_parse_malformed_import(source):
+    result, _, stderr = rlox.run(source)
+    assert rlox.failed(result, stderr)
@@ -0,0 +1,76 @@
+import pytest
+
+from tests.rlox import rlox
+
+def test_should_inherit_methods():
+    source = 'class Doughnut {                              \n' \
+             '    cook() {                                  \n' \
+             '        print "Fry until golden brown.";      \n' \
+             '    }                                         \n' \
+             '}                                             \n' \
+             'class BostonCream < Doughnut {}               \n' \
+             'BostonCream().cook();'
+
+    result, stdout, _ = rlox.run(source)
+    assert rlox.succeeded(result, stdout, ['"Fry until golden brown."'])
+
+def test_should_override_methods():
+    source = 'class A {                         \n' \
+             '    name() { return "A"; }        \n' \
+             '}                                 \n' \
+             'class B < A {                     \n' \
+             '    name() { return "B"; }        \n' \
+             '}                                 \n' \
+             'print B().name();'
+
+    result, stdout, _ = rlox.run(source)
+    assert rlox.succeeded(result, stdout, ['"B"'])
+
+def test_should_call_super_method():
+    source = 'class A {                                     \n' \
+             '    method() { print "A method"; }            \n' \
+             '}                                             \n' \
+             'class B < A {                                 \n' \
+             '    method() { print "B method"; }            \n' \
+             '    test() { super.method(); }                \n' \
+             '}                                             \n' \
+             'class C < B {}                                \n' \
+             'C().test();'
+
+    result, stdout, _ = rlox.run(source)
+    assert rlox.succeeded(result, stdout, ['"A method"'])
+
+def test_should_call_super_initializer():
+    source = 'class A {                                     \n' \
+             '    init(value) { this.value = value; }       \n' \
+             '}                                             \n' \
+             'class B < A {                                 \n' \
+             '    init(value) { super.init(value * 2); }    \n' \
+             '}                                             \n' \
+             'print B(21).value;'
+
+    result, stdout, _ = rlox.run(source)
+    assert rlox.succeeded(result, stdout, ['42'])
+
+def test_should_not_inherit_from_itself():
+    source = 'class A < A {}'
+
+    result, _, stderr = rlox.run(source)
+    assert rlox.failed(result, stderr)
+
+def test_should_not_inherit_from_non_class():
+    source = 'var NotAClass = "so not a class";     \n' \
+             'class A < NotAClass {}'
+
+    result, _, stderr = rlox.run(source)
+    assert rlox.failed(result, stderr)
+
+def test_should_not_call_undefined_super_method():
+    source = 'class A {}                                    \n' \
+             'class B < A {                                 \n' \
+             '    test() { super.missing(); }               \n' \
+             '}                                             \n' \
+             'B().test();'
+
+    result, _, stderr = rlox.run(source)
+    assert rlox.failed(result, stderr)
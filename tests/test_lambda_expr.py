@@ -0,0 +1,41 @@
+import pytest
+
+from tests.rlox import rlox
+
+def test_should_call_lambda_from_variable():
+    source = 'var add = fun (a, b) { return a + b; };  \n' \
+             'print add(1, 2);'
+
+    result, stdout, _ = rlox.run(source)
+    assert rlox.succeeded(result, stdout, ['3'])
+
+def test_should_pass_lambda_as_argument():
+    source = 'fun apply(f, value) {                    \n' \
+             '    return f(value);                     \n' \
+             '}                                        \n' \
+             'print apply(fun (n) { return n * 2; }, 21);'
+
+    result, stdout, _ = rlox.run(source)
+    assert rlox.succeeded(result, stdout, ['42'])
+
+def test_should_return_capturing_lambda():
+    source = 'fun adder(n) {                           \n' \
+             '    return fun (x) { return x + n; };    \n' \
+             '}                                        \n' \
+             'var addTen = adder(10);                  \n' \
+             'print addTen(5);'
+
+    result, stdout, _ = rlox.run(source)
+    assert rlox.succeeded(result, stdout, ['15'])
+
+def test_should_call_lambda_expression_statement():
+    result, stdout, _ = rlox.run('fun (x) { print x; }("called");')
+    assert rlox.succeeded(result, stdout, ['"called"'])
+
+def test_should_print_anonymous_function():
+    result, stdout, _ = rlox.run('print fun () {};')
+    assert rlox.succeeded(result, stdout, ['<fn anonymous>'])
+
+def test_should_not_call_lambda_with_wrong_arity():
+    result, _, stderr = rlox.run('var f = fun (a) {}; f();')
+    assert rlox.failed(result, stderr)
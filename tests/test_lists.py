@@ -0,0 +1,105 @@
+import pytest
+
+from tests.rlox import rlox
+
+def test_should_print_list_literal():
+    result, stdout, _ = rlox.run('print [1, "two", nil, [3]];')
+    assert rlox.succeeded(result, stdout, ['[1, "two", nil, [3]]'])
+
+def test_should_print_empty_list():
+    result, stdout, _ = rlox.run('print [];')
+    assert rlox.succeeded(result, stdout, ['[]'])
+
+def test_should_index_list():
+    source = 'var xs = [10, 20, 30];   \n' \
+             'print xs[0];             \n' \
+             'print xs[1 + 1];'
+
+    result, stdout, _ = rlox.run(source)
+    assert rlox.succeeded(result, stdout, ['10', '30'])
+
+def test_should_assign_to_index():
+    source = 'var xs = [1, 2, 3];      \n' \
+             'print xs[1] = "two";     \n' \
+             'print xs;'
+
+    result, stdout, _ = rlox.run(source)
+    assert rlox.succeeded(result, stdout, ['"two"', '[1, "two", 3]'])
+
+def test_should_share_list_by_reference():
+    source = 'var xs = [1];            \n' \
+             'var ys = xs;             \n' \
+             'push(ys, 2);             \n' \
+             'print xs;'
+
+    result, stdout, _ = rlox.run(source)
+    assert rlox.succeeded(result, stdout, ['[1, 2]'])
+
+@pytest.mark.parametrize(
+    'statements,expected_output',
+    [
+        ('print len(xs);', ['3']),
+        ('print len("four");', ['4']),
+        ('push(xs, 4); print xs;', ['[3, 1, 2, 4]']),
+        ('print pop(xs); print xs;', ['2', '[3, 1]']),
+        ('insert(xs, 3, 0); print xs;', ['[3, 1, 2, 0]']),
+        ('print remove(xs, 0); print xs;', ['3', '[1, 2]']),
+        ('print slice(xs, 1, 3); print xs;', ['[1, 2]', '[3, 1, 2]']),
+        ('sort(xs); print xs;', ['[1, 2, 3]']),
+    ]
+)
+def test_should_call_list_natives(statements, expected_output):
+    source = f'var xs = [3, 1, 2]; {statements}'
+
+    result, stdout, _ = rlox.run(source)
+    assert rlox.succeeded(result, stdout, expected_output)
+
+@pytest.mark.parametrize(
+    'source',
+    [
+        'print [1, 2][2];',
+        'print [1, 2][-1];',
+        'print [1, 2][0.5];',
+        'print [1, 2]["0"];',
+        'var xs = [1]; xs[1] = 2;',
+        'print "abc"[0];',
+        'pop([]);',
+        'sort([1, "a"]);',
+        'push("not a list", 1);',
+    ]
+)
+def test_should_report_list_errors(source):
+    result, _, stderr = rlox.run(source)
+    assert rlox.failed(result, stderr)
+
+def test_should_report_out_of_range_line():
+    source = 'var xs = [];      \n' \
+             'print xs[0];'
+
+    _, _, stderr = rlox.run(source)
+    assert stderr.startswith('[line 2]')
+
+def test_should_handle_list_containing_itself():
+    result, stdout, _ = rlox.run('''
+        var xs = [1];
+        push(xs, xs);
+        print xs;
+        print [xs];
+        print xs == xs;
+        print len(xs);
+    ''')
+    assert rlox.succeeded(result, stdout, ['[1, [...]]', '[[1, [...]]]', 'true', '2'])
+
+def test_should_compare_separate_lists_containing_themselves():
+    result, stdout, _ = rlox.run('''
+        var a = [1];
+        var b = [1];
+        a[0] = a;
+        b[0] = b;
+        var c = [1, 2];
+        c[0] = c;
+        print a == b;
+        print a == c;
+        print [a] == [b];
+    ''')
+    assert rlox.succeeded(result, stdout, ['true', 'false', 'true'])
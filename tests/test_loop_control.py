@@ -0,0 +1,66 @@
+import pytest
+
+from tests.rlox import rlox
+
+def test_should_break_while_loop():
+    source = 'var i = 0;                \n' \
+             'while (true) {            \n' \
+             '    if (i == 3) break;    \n' \
+             '    print i;              \n' \
+             '    i = i + 1;            \n' \
+             '}'
+
+    result, stdout, _ = rlox.run(source)
+    assert rlox.succeeded(result, stdout, ['0', '1', '2'])
+
+def test_should_continue_while_loop():
+    source = 'var i = 0;                    \n' \
+             'while (i < 4) {               \n' \
+             '    i = i + 1;                \n' \
+             '    if (i == 2) continue;     \n' \
+             '    print i;                  \n' \
+             '}'
+
+    result, stdout, _ = rlox.run(source)
+    assert rlox.succeeded(result, stdout, ['1', '3', '4'])
+
+def test_should_break_for_loop():
+    source = 'for (var i = 0; i < 10; i = i + 1) {     \n' \
+             '    if (i == 2) break;                    \n' \
+             '    print i;                              \n' \
+             '}'
+
+    result, stdout, _ = rlox.run(source)
+    assert rlox.succeeded(result, stdout, ['0', '1'])
+
+def test_should_run_increment_on_continue():
+    source = 'for (var i = 0; i < 4; i = i + 1) {      \n' \
+             '    if (i == 1) continue;                 \n' \
+             '    print i;                              \n' \
+             '}'
+
+    result, stdout, _ = rlox.run(source)
+    assert rlox.succeeded(result, stdout, ['0', '2', '3'])
+
+def test_should_only_break_innermost_loop():
+    source = 'for (var i = 0; i < 2; i = i + 1) {          \n' \
+             '    for (var j = 0; j < 10; j = j + 1) {      \n' \
+             '        if (j == 1) break;                    \n' \
+             '        print i;                              \n' \
+             '    }                                         \n' \
+             '}'
+
+    result, stdout, _ = rlox.run(source)
+    assert rlox.succeeded(result, stdout, ['0', '1'])
+
+@pytest.mark.parametrize(
+    'source',
+    [
+        'break;',
+        'continue;',
+        'while (true) { fun f() { break; } }',
+    ]
+)
+def test_should_not_use_loop_control_outside_loop(source):
+    result, _, stderr = rlox.run(source)
+    assert rlox.failed(result, stderr)
@@ -0,0 +1,93 @@
+import pytest
+
+from tests.rlox import rlox
+
+def test_should_print_map_literal():
+    result, stdout, _ = rlox.run('print {"a": 1, 2: "two", true: nil, nil: false};')
+    assert rlox.succeeded(result, stdout, ['{"a": 1, 2: "two", true: nil, nil: false}'])
+
+def test_should_print_empty_map():
+    result, stdout, _ = rlox.run('var m = {}; print m;')
+    assert rlox.succeeded(result, stdout, ['{}'])
+
+def test_should_look_up_key():
+    source = 'var m = {"a": 1, "b": 2};    \n' \
+             'print m["a"] + m["b"];'
+
+    result, stdout, _ = rlox.run(source)
+    assert rlox.succeeded(result, stdout, ['3'])
+
+def test_should_assign_key():
+    source = 'var m = {"a": 1};        \n' \
+             'm["a"] = 10;             \n' \
+             'm["b"] = 20;             \n' \
+             'print m;'
+
+    result, stdout, _ = rlox.run(source)
+    assert rlox.succeeded(result, stdout, ['{"a": 10, "b": 20}'])
+
+def test_should_treat_equal_numbers_as_same_key():
+    source = 'var m = {0: "zero", 1: "one"};   \n' \
+             'print m[-0];                     \n' \
+             'print m[2 - 1];'
+
+    result, stdout, _ = rlox.run(source)
+    assert rlox.succeeded(result, stdout, ['"zero"', '"one"'])
+
+def test_should_compare_maps_by_content():
+    result, stdout, _ = rlox.run('print {"x": 1, "y": 2} == {"y": 2, "x": 1};')
+    assert rlox.succeeded(result, stdout, ['true'])
+
+@pytest.mark.parametrize(
+    'statements,expected_output',
+    [
+        ('print keys(m);', ['["a", "b"]']),
+        ('print values(m);', ['[1, 2]']),
+        ('print has(m, "a"); print has(m, "c");', ['true', 'false']),
+        ('print delete(m, "a"); print m;', ['true', '{"b": 2}']),
+        ('print delete(m, "c");', ['false']),
+        ('print len(m);', ['2']),
+    ]
+)
+def test_should_call_map_natives(statements, expected_output):
+    source = f'var m = {{"a": 1, "b": 2}}; {statements}'
+
+    result, stdout, _ = rlox.run(source)
+    assert rlox.succeeded(result, stdout, expected_output)
+
+@pytest.mark.parametrize(
+    'source',
+    [
+        'print {"a": 1}["b"];',
+        'var m = {}; m[clock] = 1;',
+        'fun f() {} var m = {f: 1};',
+        'print has({}, [1]);',
+        'print keys([1]);',
+    ]
+)
+def test_should_report_map_errors(source):
+    result, _, stderr = rlox.run(source)
+    assert rlox.failed(result, stderr)
+
+def test_should_print_map_containing_itself():
+    result, stdout, _ = rlox.run('''
+        var m = {"a": 1};
+        m["self"] = m;
+        print m;
+        print [m];
+    ''')
+    assert rlox.succeeded(result, stdout, ['{"a": 1, "self": {...}}', '[{"a": 1, "self": {...}}]'])
+
+def test_should_compare_maps_containing_themselves():
+    result, stdout, _ = rlox.run('''
+        var m = {"a": 1};
+        var n = {"a": 1};
+        var o = {"a": 2};
+        m["self"] = m;
+        n["self"] = n;
+        o["self"] = o;
+        print m == m;
+        print m == n;
+        print m == o;
+    ''')
+    assert rlox.succeeded(result, stdout, ['true', 'true', 'false'])
@@ -0,0 +1,130 @@
+import pytest
+
+from tests.rlox import rlox
+
+DESCRIBE = '''
+    fun describe(x) {
+        match (x) {
+            0 => print "zero";
+            1 | 2 | 3 => print "small";
+            -5..0 => print "negative";
+            4..=10 if x != 7 => print "medium";
+            "a" | "b" => { print "letter"; },
+            nil => print "nothing";
+            true => print "yes";
+            _ => print "other";
+        }
+    }
+'''
+
+@pytest.mark.parametrize(
+    'value,expected_output',
+    [
+        ('0', ['"zero"']),
+        ('2', ['"small"']),
+        ('-5', ['"negative"']),
+        ('-0.5', ['"negative"']),
+        ('4', ['"medium"']),
+        ('10', ['"medium"']),
+        ('7', ['"other"']),
+        ('10.5', ['"other"']),
+        ('"b"', ['"letter"']),
+        ('"c"', ['"other"']),
+        ('nil', ['"nothing"']),
+        ('true', ['"yes"']),
+        ('false', ['"other"']),
+        ('[1]', ['"other"']),
+    ]
+)
+def test_should_run_first_matching_arm(value, expected_output):
+    result, stdout, _ = rlox.run(DESCRIBE + f'describe({value});')
+    assert rlox.succeeded(result, stdout, expected_output)
+
+def test_should_exclude_end_of_exclusive_range():
+    result, stdout, _ = rlox.run('match (5) { 0..5 => print "in"; _ => print "out"; }')
+    assert rlox.succeeded(result, stdout, ['"out"'])
+
+def test_should_match_by_value_equality():
+    result, stdout, _ = rlox.run('''
+        var greeting = "hel" + "lo";
+        match (greeting) { "hello" => print "matched"; }
+        match (1 + 1) { 2 => print "two"; }
+    ''')
+    assert rlox.succeeded(result, stdout, ['"matched"', '"two"'])
+
+def test_should_evaluate_guard_only_after_pattern_matched():
+    result, stdout, _ = rlox.run('''
+        fun check(label) { print label; return true; }
+        match (2) {
+            1 if check("first") => print "one";
+            2 if check("second") => print "two";
+        }
+    ''')
+    assert rlox.succeeded(result, stdout, ['"second"', '"two"'])
+
+def test_should_fall_through_to_next_arm_when_guard_fails():
+    result, stdout, _ = rlox.run('''
+        var limit = 3;
+        match (5) {
+            _ if 5 < limit => print "below";
+            _ => print "above";
+        }
+    ''')
+    assert rlox.succeeded(result, stdout, ['"above"'])
+
+def test_should_evaluate_subject_once():
+    result, stdout, _ = rlox.run('''
+        var calls = 0;
+        fun next() { calls = calls + 1; return calls; }
+        match (next()) { 5 => print "five"; 1 => print "one"; }
+        print calls;
+    ''')
+    assert rlox.succeeded(result, stdout, ['"one"', '1'])
+
+def test_should_return_from_arm():
+    result, stdout, _ = rlox.run('''
+        fun name(n) {
+            match (n) {
+                1 => return "one";
+                _ => return "many";
+            }
+        }
+        print name(1);
+        print name(2);
+    ''')
+    assert rlox.succeeded(result, stdout, ['"one"', '"many"'])
+
+def test_should_fail_when_no_arm_matches():
+    result, _, stderr = rlox.run('match (42) { 1 => print "one"; }')
+    assert rlox.failed(result, stderr)
+    assert 'No match arm matches value 42.' in stderr
+
+@pytest.mark.parametrize(
+    'source',
+    [
+        'match 1 { _ => print 1; }',
+        'match (1) { x => print 1; }',
+        'match (1) { 1 print 1; }',
+        'match (1) { "a".."z" => print 1; }',
+        'match (1) { 1 => print 1; ',
+        'match (1) { 1 => print 1 2 => print 2 }',
+    ]
+)
+def test_should_not_parse_malformed_match(source):
+    result, _, stderr = rlox.run(source)
+    assert rlox.failed(result, stderr)
+
+def test_should_separate_expression_arms_with_commas():
+    result, stdout, _ = rlox.run('''
+        var last;
+        for (x in 0..4) {
+            match (x) {
+                0 => print "zero",
+                1 => last = x,
+                2 => { print "two"; },
+                _ => print "other"
+            }
+        }
+        print last;
+    ''')
+    assert rlox.succeeded(result, stdout, ['"zero"', '"two"', '"other"', '1'])
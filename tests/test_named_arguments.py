@@ -0,0 +1,83 @@
+import pytest
+
+from tests.rlox import rlox
+
+CONNECT = '''
+    fun connect(host, port = 80, secure = false) {
+        print host;
+        print port;
+        print secure;
+    }
+'''
+
+@pytest.mark.parametrize(
+    'call,expected_output',
+    [
+        ('connect(host: "a")', ['"a"', '80', 'false']),
+        ('connect("b", secure: true)', ['"b"', '80', 'true']),
+        ('connect(port: 8080, host: "c")', ['"c"', '8080', 'false']),
+        ('connect("d", 443, secure: true)', ['"d"', '443', 'true']),
+    ]
+)
+def test_should_match_named_arguments_to_parameters(call, expected_output):
+    result, stdout, _ = rlox.run(CONNECT + f'{call};')
+    assert rlox.succeeded(result, stdout, expected_output)
+
+def test_should_evaluate_arguments_in_source_order():
+    result, stdout, _ = rlox.run('''
+        fun trace(value) { print value; return value; }
+        fun pair(a, b) { return a - b; }
+        print pair(b: trace(1), a: trace(2));
+    ''')
+    assert rlox.succeeded(result, stdout, ['1', '2', '1'])
+
+def test_should_pass_named_arguments_to_initializer():
+    result, stdout, _ = rlox.run('''
+        class Point {
+            init(x, y = 0) { this.x = x; this.y = y; }
+        }
+        var p = Point(y: 2, x: 1);
+        print p.x;
+        print p.y;
+    ''')
+    assert rlox.succeeded(result, stdout, ['1', '2'])
+
+def test_should_pass_named_arguments_to_methods_and_lambdas():
+    result, stdout, _ = rlox.run('''
+        class Greeter {
+            greet(name, greeting = "hello") { return greeting + " " + name; }
+        }
+        print Greeter().greet(greeting: "hi", name: "bob");
+        var add = fun (a, b) { return a + b; };
+        print add(b: 2, a: 1);
+    ''')
+    assert rlox.succeeded(result, stdout, ['"hi bob"', '3'])
+
+@pytest.mark.parametrize(
+    'source,message',
+    [
+        ('fun f(a) {} f(b: 1);', "Unknown argument 'b' for <fn f>."),
+        ('class C {} C(a: 1);', "Unknown argument 'a' for C."),
+        ('fun f(a) {} f(1, a: 2);', "Argument 'a' was already passed by position."),
+        ('fun f(a, b) {} f(b: 1);', "Missing argument for parameter 'a'."),
+        ('fun f(...rest) {} f(rest: 1);', "Rest parameter 'rest' can't be passed by name."),
+        ('fun f(a) {} f(1, 2, a: 3);', 'Expected 1 arguments, but got 2.'),
+        ('len(list: [1]);', "Native functions don't accept named arguments."),
+    ]
+)
+def test_should_report_invalid_named_arguments(source, message):
+    result, _, stderr = rlox.run(source)
+    assert rlox.failed(result, stderr)
+    assert message in stderr
+
+@pytest.mark.parametrize(
+    'source,message',
+    [
+        ('fun f(a) {} f(a: 1, a: 2);', "Duplicate named argument 'a'."),
+        ('fun f(a, b) {} f(a: 1, 2);', "Positional argument can't follow a named argument."),
+    ]
+)
+def test_should_not_parse_malformed_named_arguments(source, message):
+    result, _, stderr = rlox.run(source)
+    assert rlox.failed(result, stderr)
+    assert message in stderr
@@ -0,0 +1,103 @@
+import pytest
+
+from tests.rlox import rlox
+
+@pytest.mark.parametrize(
+    'call,expected_output',
+    [
+        ('f(1)', ['1', '10']),
+        ('f(1, 2)', ['1', '2']),
+        ('f(1, nil)', ['1', 'nil']),
+    ]
+)
+def test_should_use_default_for_missing_argument(call, expected_output):
+    result, stdout, _ = rlox.run(f'fun f(a, b = 10) {{ print a; print b; }} {call};')
+    assert rlox.succeeded(result, stdout, expected_output)
+
+def test_should_evaluate_default_on_every_call():
+    result, stdout, _ = rlox.run('''
+        var calls = 0;
+        fun count() { calls = calls + 1; return calls; }
+        fun f(n = count()) { return n; }
+        print f();
+        print f(100);
+        print f();
+    ''')
+    assert rlox.succeeded(result, stdout, ['1', '100', '2'])
+
+def test_should_let_default_use_earlier_parameters():
+    result, stdout, _ = rlox.run('fun f(a, b = a * 2, c = a + b) { print c; } f(3);')
+    assert rlox.succeeded(result, stdout, ['9'])
+
+def test_should_evaluate_default_in_closure():
+    result, stdout, _ = rlox.run('''
+        fun make(step) {
+            fun next(n, by = step) { return n + by; }
+            return next;
+        }
+        var step = 100;
+        var next = make(5);
+        print next(1);
+    ''')
+    assert rlox.succeeded(result, stdout, ['6'])
+
+@pytest.mark.parametrize(
+    'call,expected_output',
+    [
+        ('f(1)', ['[]']),
+        ('f(1, 2)', ['[2]']),
+        ('f(1, 2, 3, 4)', ['[2, 3, 4]']),
+    ]
+)
+def test_should_collect_rest_arguments_into_list(call, expected_output):
+    result, stdout, _ = rlox.run(f'fun f(first, ...rest) {{ print rest; }} {call};')
+    assert rlox.succeeded(result, stdout, expected_output)
+
+def test_should_combine_defaults_and_rest():
+    result, stdout, _ = rlox.run('''
+        var log = fun (message, level = "info", ...tags) {
+            print level + ": " + message;
+            print len(tags);
+        };
+        log("started");
+        log("failed", "error", "db", "retry");
+    ''')
+    assert rlox.succeeded(result, stdout, ['"info: started"', '0', '"error: failed"', '2'])
+
+def test_should_use_defaults_in_initializer():
+    result, stdout, _ = rlox.run('''
+        class Point {
+            init(x = 0, y = x) { this.x = x; this.y = y; }
+        }
+        var p = Point(3);
+        print p.x + p.y;
+        print Point().y;
+    ''')
+    assert rlox.succeeded(result, stdout, ['6', '0'])
+
+@pytest.mark.parametrize(
+    'source,message',
+    [
+        ('fun f(a, b = 1) {} f();', 'Expected 1 to 2 arguments, but got 0.'),
+        ('fun f(a, b = 1) {} f(1, 2, 3);', 'Expected 1 to 2 arguments, but got 3.'),
+        ('fun f(a, ...rest) {} f();', 'Expected at least 1 arguments, but got 0.'),
+        ('fun f(a, b) {} f(1);', 'Expected 2 arguments, but got 1.'),
+    ]
+)
+def test_should_report_accepted_argument_range(source, message):
+    result, _, stderr = rlox.run(source)
+    assert rlox.failed(result, stderr)
+    assert message in stderr
+
+@pytest.mark.parametrize(
+    'source',
+    [
+        'fun f(a = 1, b) {}',
+        'fun f(...rest, a) {}',
+        'fun f(...rest = 1) {}',
+        'fun f(...) {}',
+    ]
+)
+def test_should_not_parse_malformed_parameters(source):
+    result, _, stderr = rlox.run(source)
+    assert rlox.failed(result, stderr)
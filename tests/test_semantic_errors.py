@@ -0,0 +1,28 @@
+import pytest
+
+from tests.rlox import rlox
+
+@pytest.mark.parametrize(
+    'source',
+    [
+        'print "before"; return 1;',
+        'var a = 1; { var a = a; print "unreachable"; }',
+        '{ var a = 1; var a = 2; print "unreachable"; }',
+        'fun f(a, a) {} print "unreachable";',
+        'class A { init() { return 1; } } print "unreachable";',
+    ]
+)
+def test_should_not_run_script_with_semantic_error(source):
+    result, stdout, stderr = rlox.run(source)
+    assert result == rlox.FAILURE
+    assert stdout == ''
+    assert stderr != ''
+
+def test_should_report_error_line():
+    source = 'var a = 1;        \n' \
+             '{                 \n' \
+             '    var a = a;    \n' \
+             '}'
+
+    _, _, stderr = rlox.run(source)
+    assert stderr.startswith('line 3')
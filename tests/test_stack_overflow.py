@@ -0,0 +1,161 @@
+import pytest
+
+from tests.rlox import rlox
+
+RECURSE = 'fun recurse(n) { if (n == 0) return 0; return 1 + recurse(n - 1); }'
+
+def test_should_report_stack_overflow_for_unbounded_recursion():
+    result, _, stderr = rlox.run('''
+        fun forever(n) {
+            return 1 + forever(n + 1);
+        }
+        forever(0);
+    ''')
+    assert rlox.failed(result, stderr)
+    assert '[line 3] InterpretError: Stack overflow.' in stderr
+
+@pytest.mark.parametrize(
+    'depth,limit,expected_output',
+    [
+        (999, None, '999'),
+        (49, '50', '49'),
+        (5000, '6000', '5000'),
+    ]
+)
+def test_should_allow_calls_within_max_depth(depth, limit, expected_output):
+    arguments = () if limit is None else ('--max-call-depth', limit)
+    result, stdout, _ = rlox.run(f'{RECURSE} print recurse({depth});', arguments)
+    assert rlox.succeeded(result, stdout, [expected_output])
+
+@pytest.mark.parametrize('depth,limit', [(1000, None), (50, '50'), (1, '1')])
+def test_should_report_stack_overflow_past_max_depth(depth, limit):
+    arguments = () if limit is None else ('--max-call-depth', limit)
+    result, _, stderr = rlox.run(f'{RECURSE} print recurse({depth});', arguments)
+    assert rlox.failed(result, stderr)
+    assert 'Stack overflow.' in stderr
+
+def test_should_not_count_tail_calls_towards_depth():
+    result, stdout, _ = rlox.run('''
+        fun count(n) {
+            if (n == 0) return "done";
+            return count(n - 1);
+        }
+        print count(100);
+    ''', ('--max-call-depth', '10'))
+    assert rlox.succeeded(result, stdout, ['"done"'])
+
+def test_should_catch_stack_overflow_and_keep_running():
+    result, stdout, _ = rlox.run(f'''
+        {RECURSE}
+        try {{
+            recurse(100000);
+        }} catch (error) {{
+            print error.message;
+        }}
+        print recurse(900);
+    ''')
+    assert rlox.succeeded(result, stdout, ['"Stack overflow."', '900'])
+
+def test_should_keep_prompt_running_after_stack_overflow():
+    result, stdout, stderr = rlox.run_prompt([
+        'fun forever() { return 1 + forever(); }',
+        'forever();',
+        'print "still here";',
+    ])
+    assert result == rlox.SUCCESS
+    assert 'Stack overflow.' in stderr
+    assert '"still here"' in stdout
+
+def test_should_report_stack_overflow_for_returned_call_inside_try():
+    result, _, stderr = rlox.run('''
+        fun f(n) {
+            if (n == 0) return "done";
+            try {
+                return f(n - 1);
+            } finally {}
+        }
+        print f(1000000);
+    ''', ('--max-call-depth', '10'))
+    assert rlox.failed(result, stderr)
+    assert 'Stack overflow.' in stderr
+
+def test_should_count_iterator_protocol_calls_towards_depth():
+    result, stdout, _ = rlox.run('''
+        class Endless {
+            iterator() {
+                for (x in Endless()) {}
+                return [];
+            }
+        }
+        try {
+            for (x in Endless()) {}
+        } catch (error) {
+            print error.message;
+        }
+    ''')
+    assert rlox.succeeded(result, stdout, ['"Stack overflow."'])
+
+def test_should_count_generator_resumes_towards_depth():
+    result, stdout, _ = rlox.run('''
+        fun nested(n) {
+            for (x in nested(n + 1)) yield x;
+            yield n;
+        }
+        try {
+            for (x in nested(0)) print x;
+        } catch (error) {
+            print error.message;
+        }
+    ''')
+    assert rlox.succeeded(result, stdout, ['"Stack overflow."'])
+
+def test_should_allow_largest_max_depth():
+    result, stdout, _ = rlox.run(f'{RECURSE} print recurse(9999);', ('--max-call-depth', '10000'))
+    assert rlox.succeeded(result, stdout, ['9999'])
+
+@pytest.mark.parametrize('limit', ['0', '10001', '100000', '18446744073709551615', 'deep'])
+def test_should_reject_out_of_range_max_depth(limit):
+    result, stdout, stderr = rlox.run(f'{RECURSE} print recurse(1);', ('--max-call-depth', limit))
+    assert result != rlox.SUCCESS
+    assert stdout == ''
+    assert "invalid value" in stderr and "--max-call-depth" in stderr
+
+NESTED_BODIES = {
+    'loops': lambda body: f'while (true) {{ try {{ {body} }} finally {{}} break; }}',
+    'conditionals': lambda body: f'if (true) {{ {body} }}',
+    'blocks': lambda body: f'{{ var x = 1; {body} }}',
+    'groupings': lambda body: f'({body[:-1]});',
+}
+
+@pytest.mark.parametrize(
+    'nesting,width',
+    [
+        ('loops', 20),
+        ('conditionals', 60),
+        ('blocks', 60),
+        ('groupings', 150),
+        ('conditionals', 400),
+    ]
+)
+def test_should_report_stack_overflow_for_recursion_through_nested_bodies(nesting, width):
+    body = 'nested(n - 1);'
+    for _ in range(width):
+        body = NESTED_BODIES[nesting](body)
+
+    result, stdout, stderr = rlox.run(f'''
+        fun nested(n) {{
+            if (n == 0) return 0;
+            {body}
+            return 0;
+        }}
+        try {{
+            nested(990);
+        }} catch (error) {{
+            print error.message;
+        }}
+        nested(990);
+        print "unreachable";
+    ''')
+    assert result != rlox.SUCCESS
+    assert stdout == '"Stack overflow."'
+    assert 'Stack overflow.' in stderr
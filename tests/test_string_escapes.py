@@ -0,0 +1,39 @@
+import pytest
+
+from tests.rlox import rlox
+
+def test_should_decode_newline_and_tab():
+    result, stdout, _ = rlox.run(r'print "one\ntwo\tthree";')
+    assert rlox.succeeded(result, stdout, ['"one', 'two\tthree"'])
+
+def test_should_decode_quote_and_backslash():
+    result, stdout, _ = rlox.run(r'print "say \"hi\" \\ bye";')
+    assert rlox.succeeded(result, stdout, [r'"say "hi" \ bye"'])
+
+def test_should_decode_unicode_escape():
+    result, stdout, _ = rlox.run(r'print "\u{48}\u{49} \u{2713}";')
+    assert rlox.succeeded(result, stdout, ['"HI ✓"'])
+
+def test_should_count_decoded_characters():
+    result, stdout, _ = rlox.run(r'print len("a\n\u{1F431}");')
+    assert rlox.succeeded(result, stdout, ['3'])
+
+@pytest.mark.parametrize(
+    'source',
+    [
+        r'print "\q";',
+        r'print "\u41";',
+        r'print "\u{}";',
+        r'print "\u{110000}";',
+        r'print "\u{41";',
+    ]
+)
+def test_should_report_invalid_escape(source):
+    result, stdout, stderr = rlox.run(source)
+    assert result == rlox.FAILURE
+    assert stdout == ''
+    assert stderr != ''
+
+def test_should_report_invalid_escape_line():
+    _, _, stderr = rlox.run('var a = 1;\nprint "\\x";')
+    assert stderr.startswith('line 2')
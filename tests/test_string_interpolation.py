@@ -0,0 +1,55 @@
+import pytest
+
+from tests.rlox import rlox
+
+def test_should_interpolate_expression():
+    source = 'var a = 2;                        \n' \
+             'var b = 3;                        \n' \
+             'print "total: ${a + b} items";'
+
+    result, stdout, _ = rlox.run(source)
+    assert rlox.succeeded(result, stdout, ['"total: 5 items"'])
+
+def test_should_interpolate_strings_without_quotes():
+    source = 'var name = "Lox";                 \n' \
+             'print "Hello, ${name}!";'
+
+    result, stdout, _ = rlox.run(source)
+    assert rlox.succeeded(result, stdout, ['"Hello, Lox!"'])
+
+@pytest.mark.parametrize(
+    'expression,expected_output',
+    [
+        ('"${nil} ${true} ${1.5}"', ['"nil true 1.5"']),
+        ('"${[1, "a"]}"', ['"[1, "a"]"']),
+        ('"${clock}"', ['"<fn native>"']),
+        ('"${1}${2}"', ['"12"']),
+    ]
+)
+def test_should_stringify_like_print(expression, expected_output):
+    result, stdout, _ = rlox.run(f'print {expression};')
+    assert rlox.succeeded(result, stdout, expected_output)
+
+def test_should_interpolate_nested_strings():
+    result, stdout, _ = rlox.run('print "outer ${ "inner ${1 + 1}" } done";')
+    assert rlox.succeeded(result, stdout, ['"outer inner 2 done"'])
+
+def test_should_interpolate_map_literal():
+    result, stdout, _ = rlox.run('print "${ {"k": 42}["k"] }";')
+    assert rlox.succeeded(result, stdout, ['"42"'])
+
+def test_should_escape_interpolation():
+    result, stdout, _ = rlox.run(r'print "\${not interpolated}";')
+    assert rlox.succeeded(result, stdout, ['"${not interpolated}"'])
+
+@pytest.mark.parametrize(
+    'source',
+    [
+        'print "${}";',
+        'print "${1";',
+        'print "${1 +}";',
+    ]
+)
+def test_should_report_invalid_interpolation(source):
+    result, _, stderr = rlox.run(source)
+    assert rlox.failed(result, stderr)
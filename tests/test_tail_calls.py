@@ -0,0 +1,110 @@
+import pytest
+
+from tests.rlox import rlox
+
+def test_should_run_deep_tail_recursion_in_constant_stack():
+    result, stdout, _ = rlox.run('''
+        fun count(n, acc) {
+            if (n == 0) return acc;
+            return count(n - 1, acc + 1);
+        }
+        print count(1000000, 0);
+    ''')
+    assert rlox.succeeded(result, stdout, ['1000000'])
+
+def test_should_run_deep_mutual_tail_recursion():
+    result, stdout, _ = rlox.run('''
+        fun isEven(n) {
+            if (n == 0) return true;
+            return isOdd(n - 1);
+        }
+        fun isOdd(n) {
+            if (n == 0) return false;
+            return isEven(n - 1);
+        }
+        print isEven(100001);
+    ''')
+    assert rlox.succeeded(result, stdout, ['false'])
+
+@pytest.mark.parametrize(
+    'returned',
+    [
+        'n == 0 ? "done" : down(n - 1)',
+        '(n == 0 ? "done" : (down(n - 1)))',
+    ]
+)
+def test_should_treat_conditional_branches_as_tail_position(returned):
+    result, stdout, _ = rlox.run(f'''
+        fun down(n) {{ return {returned}; }}
+        print down(100000);
+    ''')
+    assert rlox.succeeded(result, stdout, ['"done"'])
+
+def test_should_tail_call_methods_and_closures():
+    result, stdout, _ = rlox.run('''
+        class Counter {
+            down(n) {
+                if (n == 0) return this;
+                return this.down(n - 1);
+            }
+        }
+        fun make() {
+            fun inner(n) {
+                if (n == 0) return "inner";
+                return inner(n - 1);
+            }
+            return inner;
+        }
+        print Counter().down(100000);
+        print make()(100000);
+    ''')
+    assert rlox.succeeded(result, stdout, ['Counter instance', '"inner"'])
+
+def test_should_pass_named_and_default_arguments_in_tail_call():
+    result, stdout, _ = rlox.run('''
+        fun sum(n, acc = 0) {
+            if (n == 0) return acc;
+            return sum(acc: acc + n, n: n - 1);
+        }
+        print sum(100000);
+    ''')
+    assert rlox.succeeded(result, stdout, ['5000050000'])
+
+def test_should_return_value_of_native_and_class_calls():
+    result, stdout, _ = rlox.run('''
+        class Point { init(x) { this.x = x; } }
+        fun make(x) { return Point(x); }
+        fun size(list) { return len(list); }
+        print make(3).x;
+        print size([1, 2]);
+    ''')
+    assert rlox.succeeded(result, stdout, ['3', '2'])
+
+def test_should_catch_error_from_tail_call_inside_try():
+    result, stdout, _ = rlox.run('''
+        fun fail() { throw "boom"; }
+        fun attempt() {
+            try {
+                return fail();
+            } catch (error) {
+                return "caught " + error;
+            } finally {
+                print "finally";
+            }
+        }
+        print attempt();
+    ''')
+    assert rlox.succeeded(result, stdout, ['"finally"', '"caught boom"'])
+
+@pytest.mark.parametrize(
+    'source,message',
+    [
+        ('fun one(a) { return a; } fun f() { return one(1, 2); } f();', 'Expected 1 arguments, but got 2.'),
+        ('fun one(a) { return a; } fun f() { return one(b: 1); } f();', "Unknown argument 'b' for <fn one>."),
+        ('fun f() { return nil(); } f();', 'Can only call functions and classes'),
+    ]
+)
+def test_should_report_runtime_error_from_tail_call(source, message):
+    result, _, stderr = rlox.run(source)
+    assert rlox.failed(result, stderr)
+    assert message in stderr